@@ -0,0 +1,90 @@
+//! Decoder benchmarks.  Run with `cargo bench`.
+//!
+//! Uses only `std`, so it runs on stable Rust without dependencies.
+use jtoo::{validate, Decoder};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const MIN_DURATION: Duration = Duration::from_millis(500);
+
+/// Runs `f` repeatedly for at least [`MIN_DURATION`] and prints the time per iteration
+/// and the throughput over `input`.
+#[allow(clippy::cast_precision_loss)]
+fn bench(name: &str, input: &[u8], mut f: impl FnMut(&[u8])) {
+    f(input);
+    let start = Instant::now();
+    let mut iterations = 0u32;
+    while start.elapsed() < MIN_DURATION {
+        f(black_box(input));
+        iterations += 1;
+    }
+    let per_iteration = start.elapsed() / iterations;
+    let megabytes_per_second = input.len() as f64 / per_iteration.as_secs_f64() / 1_000_000.0;
+    println!("{name:32} {per_iteration:>12.2?}/iter {megabytes_per_second:>10.1} MB/s");
+}
+
+fn list_of(item: &str, count: usize) -> Vec<u8> {
+    format!("[{}]", vec![item; count].join(",")).into_bytes()
+}
+
+fn decode_list(input: &[u8], mut consume: impl FnMut(&mut Decoder)) {
+    let mut decoder = Decoder::new(input);
+    decoder.consume_list_open().unwrap();
+    while decoder.has_another_list_item() {
+        consume(&mut decoder);
+    }
+    decoder.consume_list_close().unwrap();
+    decoder.close().unwrap();
+}
+
+fn main() {
+    let strings = list_of(&format!("\"{}\"", "abcdefghij".repeat(10)), 1_000);
+    bench("strings", &strings, |input| {
+        decode_list(input, |decoder| {
+            black_box(decoder.consume_str().unwrap());
+        });
+    });
+    bench("strings_owned", &strings, |input| {
+        decode_list(input, |decoder| {
+            black_box(decoder.consume_string().unwrap());
+        });
+    });
+    let escaped_strings = list_of(&format!("\"{}\"", "abcd\\22efgh\\0a".repeat(10)), 1_000);
+    bench("strings_escaped", &escaped_strings, |input| {
+        decode_list(input, |decoder| {
+            black_box(decoder.consume_str().unwrap());
+        });
+    });
+    let byte_strings = list_of(&format!("B{}", "0123456789abcdef".repeat(8)), 1_000);
+    bench("byte_strings", &byte_strings, |input| {
+        decode_list(input, |decoder| {
+            black_box(decoder.consume_byte_string().unwrap());
+        });
+    });
+    let integers = list_of("-1_234_567_890", 10_000);
+    bench("integers", &integers, |input| {
+        decode_list(input, |decoder| {
+            black_box(decoder.consume_integer().unwrap());
+        });
+    });
+    let decimals = list_of("1_234.567_8", 10_000);
+    bench("decimals", &decimals, |input| {
+        decode_list(input, |decoder| {
+            black_box(decoder.consume_decimal().unwrap());
+        });
+    });
+    let deep_lists = format!("{}{}", "[".repeat(1_000), "]".repeat(1_000)).into_bytes();
+    bench("deep_lists", &deep_lists, |input| {
+        let mut decoder = Decoder::new(input);
+        decoder.skip_value().unwrap();
+        decoder.close().unwrap();
+    });
+    for (name, input) in [
+        ("validate_strings", &strings),
+        ("validate_byte_strings", &byte_strings),
+        ("validate_integers", &integers),
+        ("validate_deep_lists", &deep_lists),
+    ] {
+        bench(name, input, |input| validate(input).unwrap());
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Decode, DecodeError, Decoder, Encode, EncodeError, Encoder, ErrorReason};
+use core::ops::{Deref, DerefMut};
+
+fn encode_byte_string(bytes: &[u8], encoder: &mut Encoder) -> Result<(), EncodeError> {
+    encoder.open_byte_string()?;
+    encoder.append_byte_string(bytes)?;
+    encoder.close_byte_string()
+}
+
+/// A borrowed byte slice that encodes as a byte string `B0a1b2c`, not a list of integers.
+///
+/// # Example
+/// ```
+/// use jtoo::{Bytes, Encode};
+/// assert_eq!(Bytes(&[0x0a, 0x1b, 0x2c]).encode(), Ok("B0a1b2c".to_string()));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Bytes<'a>(pub &'a [u8]);
+impl<'a> From<&'a [u8]> for Bytes<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+}
+impl Deref for Bytes<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0
+    }
+}
+impl AsRef<[u8]> for Bytes<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.0
+    }
+}
+impl Encode for Bytes<'_> {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_byte_string(self.0, encoder)
+    }
+}
+
+/// An owned byte vector that encodes and decodes as a byte string `B0a1b2c`,
+/// not a list of integers.
+///
+/// # Example
+/// ```
+/// use jtoo::{ByteBuf, Decode, Encode};
+/// let value = ByteBuf(vec![0x0a, 0x1b, 0x2c]);
+/// assert_eq!(value.encode(), Ok("B0a1b2c".to_string()));
+/// assert_eq!(ByteBuf::decode(b"B0a1b2c"), Ok(value));
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ByteBuf(pub Vec<u8>);
+impl ByteBuf {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+impl From<Vec<u8>> for ByteBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+impl From<&[u8]> for ByteBuf {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+impl From<ByteBuf> for Vec<u8> {
+    fn from(value: ByteBuf) -> Self {
+        value.0
+    }
+}
+impl Deref for ByteBuf {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+impl DerefMut for ByteBuf {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+impl AsRef<[u8]> for ByteBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+impl Encode for ByteBuf {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_byte_string(&self.0, encoder)
+    }
+}
+impl<'a> Decode<'a> for ByteBuf {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_byte_string().map(Self)
+    }
+}
+
+impl<const N: usize> Encode for [u8; N] {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_byte_string(self, encoder)
+    }
+}
+impl<'a, const N: usize> Decode<'a> for [u8; N] {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_map(Decoder::consume_byte_string, |bytes| {
+            Self::try_from(bytes).map_err(|_| ErrorReason::IncorrectByteStringLength)
+        })
+    }
+}
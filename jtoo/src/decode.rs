@@ -1,83 +1,301 @@
 use crate::escape_ascii;
-use core::fmt::Debug;
+use crate::time::{days_in_month, weeks_in_year};
+use core::fmt::{Debug, Display};
+use core::marker::PhantomData;
+use std::borrow::Cow;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ErrorReason {
+    ByteStringTooLong,
     DataNotConsumed,
+    DayNotInMonth,
     DayOutOfRange,
+    DecimalTooLarge,
+    DecimalTooPrecise,
+    DurationTooPrecise,
+    EscapedStringNotBorrowable,
     ExpectedBool,
     ExpectedByteString,
     ExpectedDateOrTime,
+    ExpectedDecimal,
     ExpectedInteger,
     ExpectedList,
     ExpectedListEnd,
     ExpectedListSeparator,
     ExpectedSingleZero,
     ExpectedString,
+    ExpectedTimestamp,
+    ExpectedValue,
     HourOutOfRange,
     IncompleteEscapeSequence,
+    IncorrectByteStringLength,
     IncorrectDigitGrouping,
+    InputTooLarge,
     IntegerTooLarge,
     InvalidEscapeSequence,
+    InvalidTimestampPrecision,
     ListCloseNotConsumed,
+    ListTooDeep,
     MalformedBool,
     MalformedByteString,
     MalformedDate,
     MalformedDateTimeTzOffset,
+    MalformedDecimal,
     MalformedInteger,
+    MalformedIpAddress,
     MalformedListEnd,
+    MalformedSocketAddress,
     MalformedString,
     MalformedTime,
     MalformedTimeZoneOffset,
+    MalformedTimestamp,
     MinuteOutOfRange,
     MonthOutOfRange,
+    NegativeNotAllowed,
     NegativeZero,
     NotInList,
     NotUtf8,
     SecondOutOfRange,
+    StringTooLong,
+    TimestampTooLarge,
     TimezoneOffsetHourOutOfRange,
     TimezoneOffsetMinuteOutOfRange,
+    TooManyItems,
+    TooManyWarnings,
     UnclosedString,
     UppercaseHexNotAllowedInByteString,
+    WeekNotInYear,
     WeekOutOfRange,
     YearOutOfRange,
     ZeroTimeZoneMinutesShouldBeOmitted,
     ZeroTimeZoneOffsetShouldBeZ,
 }
 
+impl Display for ErrorReason {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            ErrorReason::ByteStringTooLong => "byte string is too long",
+            ErrorReason::DataNotConsumed => "data after the end of the value",
+            ErrorReason::DayNotInMonth => "day is past the end of the month",
+            ErrorReason::DayOutOfRange => "day is out of range",
+            ErrorReason::DecimalTooLarge => "decimal is too large",
+            ErrorReason::DecimalTooPrecise => "decimal has too many fraction digits",
+            ErrorReason::DurationTooPrecise => "duration is more precise than nanoseconds",
+            ErrorReason::EscapedStringNotBorrowable => {
+                "string contains escape sequences and cannot be borrowed"
+            }
+            ErrorReason::ExpectedBool => "expected a bool",
+            ErrorReason::ExpectedByteString => "expected a byte string",
+            ErrorReason::ExpectedDateOrTime => "expected a date or time",
+            ErrorReason::ExpectedDecimal => "expected a decimal",
+            ErrorReason::ExpectedInteger => "expected an integer",
+            ErrorReason::ExpectedList => "expected a list",
+            ErrorReason::ExpectedListEnd => "expected the end of a list",
+            ErrorReason::ExpectedListSeparator => "expected a comma or the end of the list",
+            ErrorReason::ExpectedSingleZero => "integer has a leading zero",
+            ErrorReason::ExpectedString => "expected a string",
+            ErrorReason::ExpectedTimestamp => "expected a timestamp",
+            ErrorReason::ExpectedValue => "expected a value",
+            ErrorReason::HourOutOfRange => "hour is out of range",
+            ErrorReason::IncompleteEscapeSequence => "incomplete escape sequence",
+            ErrorReason::IncorrectByteStringLength => "byte string has the wrong length",
+            ErrorReason::IncorrectDigitGrouping => "digits are not in groups of three",
+            ErrorReason::InputTooLarge => "input is too large",
+            ErrorReason::IntegerTooLarge => "integer is too large",
+            ErrorReason::InvalidEscapeSequence => "invalid escape sequence",
+            ErrorReason::InvalidTimestampPrecision => {
+                "timestamp fraction must have 3, 6, or 9 digits"
+            }
+            ErrorReason::ListCloseNotConsumed => "list is not closed",
+            ErrorReason::ListTooDeep => "lists are nested too deeply",
+            ErrorReason::MalformedBool => "malformed bool",
+            ErrorReason::MalformedByteString => "malformed byte string",
+            ErrorReason::MalformedDate => "malformed date",
+            ErrorReason::MalformedDateTimeTzOffset => "malformed date, time, or timezone offset",
+            ErrorReason::MalformedDecimal => "malformed decimal",
+            ErrorReason::MalformedInteger => "malformed integer",
+            ErrorReason::MalformedIpAddress => "malformed IP address",
+            ErrorReason::MalformedListEnd => "malformed list end",
+            ErrorReason::MalformedSocketAddress => "malformed socket address",
+            ErrorReason::MalformedString => "malformed string",
+            ErrorReason::MalformedTime => "malformed time",
+            ErrorReason::MalformedTimeZoneOffset => "malformed timezone offset",
+            ErrorReason::MalformedTimestamp => "malformed timestamp",
+            ErrorReason::MinuteOutOfRange => "minute is out of range",
+            ErrorReason::MonthOutOfRange => "month is out of range",
+            ErrorReason::NegativeNotAllowed => "negative value is not allowed",
+            ErrorReason::NegativeZero => "negative zero is not allowed",
+            ErrorReason::NotInList => "not in a list",
+            ErrorReason::NotUtf8 => "string is not valid UTF-8",
+            ErrorReason::SecondOutOfRange => "second is out of range",
+            ErrorReason::StringTooLong => "string is too long",
+            ErrorReason::TimestampTooLarge => "timestamp is too large",
+            ErrorReason::TimezoneOffsetHourOutOfRange => "timezone offset hour is out of range",
+            ErrorReason::TimezoneOffsetMinuteOutOfRange => "timezone offset minute is out of range",
+            ErrorReason::TooManyItems => "too many items",
+            ErrorReason::TooManyWarnings => "too many warnings",
+            ErrorReason::UnclosedString => "string is not closed",
+            ErrorReason::UppercaseHexNotAllowedInByteString => {
+                "byte string has uppercase hex digits"
+            }
+            ErrorReason::WeekNotInYear => "week is past the end of the ISO week-numbering year",
+            ErrorReason::WeekOutOfRange => "week is out of range",
+            ErrorReason::YearOutOfRange => "year is out of range",
+            ErrorReason::ZeroTimeZoneMinutesShouldBeOmitted => {
+                "timezone offset minutes of zero should be omitted"
+            }
+            ErrorReason::ZeroTimeZoneOffsetShouldBeZ => {
+                "zero timezone offset should be written as Z"
+            }
+        };
+        f.write_str(message)
+    }
+}
+
+/// One step of the path from the top-level value to the value that failed to decode.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum PathSegment {
+    /// A list item, rendered as `[3]`.
+    Index(usize),
+    /// A list item labeled with [`Decoder::set_field`], rendered as `.name`.
+    Field(&'static str),
+}
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathSegment::Index(index) => write!(f, "[{index}]"),
+            PathSegment::Field(name) => write!(f, ".{name}"),
+        }
+    }
+}
+
 #[allow(clippy::module_name_repetitions)]
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct DecodeError {
     pub reason: ErrorReason,
+    /// Up to 30 bytes of input, starting at the failed value.
     pub debug_bytes: Vec<u8>,
+    /// The number of input bytes before `debug_bytes`.
+    pub offset: usize,
+    /// The 1-based line number of `offset`.
+    pub line: usize,
+    /// The 1-based byte column of `offset`.
+    pub column: usize,
+    /// The list items and fields that contain the failed value.
+    /// Empty for a top-level value.
+    pub path: Vec<PathSegment>,
+    /// Descriptions added with [`DecodeError::with_context`], innermost first.
+    /// They are shown by `Display` and ignored by `==`.
+    pub context: Vec<Cow<'static, str>>,
 }
-impl Debug for DecodeError {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
+impl PartialEq for DecodeError {
+    fn eq(&self, other: &Self) -> bool {
+        self.reason == other.reason
+            && self.debug_bytes == other.debug_bytes
+            && self.offset == other.offset
+            && self.line == other.line
+            && self.column == other.column
+            && self.path == other.path
+    }
+}
+impl Eq for DecodeError {}
+impl DecodeError {
+    /// Renders `path` like `[3].items[12].price`.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::{Decode, ErrorReason};
+    /// let err = <[u8; 1]>::decode(b"B0102").unwrap_err();
+    /// assert_eq!(err.path_string(), "");
+    /// ```
+    #[must_use]
+    pub fn path_string(&self) -> String {
+        self.path.iter().map(ToString::to_string).collect()
+    }
+
+    /// Adds a description of the value being decoded, like a type or field name.
+    /// Call it as the error passes out of each decoder.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::{Decode, Decoder};
+    /// let err = Decoder::new(b"x")
+    ///     .consume_integer()
+    ///     .map_err(|e| e.with_context("Item.price"))
+    ///     .map_err(|e| e.with_context("Order"))
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "expected an integer at line 1, column 1 (offset 0) while decoding Item.price \
+    ///     while decoding Order: 'x'"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_context(mut self, context: impl Into<Cow<'static, str>>) -> Self {
+        self.context.push(context.into());
+        self
+    }
+
+    fn fmt_location(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "DecodeError: {:?}: '{}'",
-            self.reason,
-            escape_ascii(&self.debug_bytes)
-        )
+            " at line {}, column {} (offset {})",
+            self.line, self.column, self.offset
+        )?;
+        if !self.path.is_empty() {
+            write!(f, " in {}", self.path_string())?;
+        }
+        for context in &self.context {
+            write!(f, " while decoding {context}")?;
+        }
+        write!(f, ": '{}'", escape_ascii(&self.debug_bytes))
+    }
+}
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.reason)?;
+        self.fmt_location(f)
+    }
+}
+impl Debug for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DecodeError: {:?}", self.reason)?;
+        self.fmt_location(f)
     }
 }
+impl std::error::Error for DecodeError {}
 
-pub trait Decode {
+/// A type that can be decoded from JTOO bytes.
+/// The lifetime `'a` lets the value borrow from the input, like `&'a str`.
+pub trait Decode<'a> {
     #[allow(clippy::missing_errors_doc)]
-    fn decode_using(decoder: &mut Decoder) -> Result<Self, DecodeError>
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError>
     where
         Self: Sized;
 
     #[allow(clippy::missing_errors_doc)]
-    fn decode(bytes: &[u8]) -> Result<Self, DecodeError>
+    fn decode(bytes: &'a [u8]) -> Result<Self, DecodeError>
     where
         Self: Sized,
     {
         let mut decoder = Decoder::new(bytes);
         Self::decode_using(&mut decoder)
     }
+
+    #[allow(clippy::missing_errors_doc)]
+    fn decode_with_options(bytes: &'a [u8], options: DecoderOptions) -> Result<Self, DecodeError>
+    where
+        Self: Sized,
+    {
+        let mut decoder = Decoder::with_options(bytes, options)?;
+        Self::decode_using(&mut decoder)
+    }
 }
 
+/// A type that can be decoded without borrowing from the input.
+pub trait DecodeOwned: for<'a> Decode<'a> {}
+impl<T> DecodeOwned for T where T: for<'a> Decode<'a> {}
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
 pub enum Date {
     Year { y: u16 },
@@ -259,26 +477,537 @@ impl DateTimeTzOffset {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub enum TimestampPrecision {
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds,
+}
+impl TimestampPrecision {
+    /// The number of units in one second, `1` for `Seconds` and `1_000_000_000` for `Nanoseconds`.
+    #[must_use]
+    pub fn units_per_second(&self) -> u32 {
+        match self {
+            TimestampPrecision::Seconds => 1,
+            TimestampPrecision::Milliseconds => 1_000,
+            TimestampPrecision::Microseconds => 1_000_000,
+            TimestampPrecision::Nanoseconds => 1_000_000_000,
+        }
+    }
+}
+
+/// A count of seconds, milliseconds, microseconds, or nanoseconds since the Unix epoch.
+/// Negative values are before the epoch.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub struct Timestamp {
+    pub value: i64,
+    pub precision: TimestampPrecision,
+}
+
+/// The type of the next value in a [`Decoder`], from [`Decoder::peek_kind`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ValueKind {
+    Bool,
+    Integer,
+    Decimal,
+    String,
+    ByteString,
+    /// A date, time, or timezone offset, like `D2024-01-01` or `T12:00`.
+    DateTime,
+    Timestamp,
+    List,
+    ListEnd,
+    /// The buffer is empty.
+    End,
+}
+
+/// A saved decoder position, from [`Decoder::checkpoint`].
+#[derive(Clone, Debug)]
+pub struct Checkpoint<'a> {
+    input: &'a [u8],
+    bytes: &'a [u8],
+    debug_bytes: &'a [u8],
+    lists: Vec<ListLevel>,
+    item_count: usize,
+    warning_count: usize,
+    in_records: bool,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ListLevel {
+    index: usize,
+    field: Option<&'static str>,
+}
+impl ListLevel {
+    fn segment(self) -> PathSegment {
+        match self.field {
+            Some(name) => PathSegment::Field(name),
+            None => PathSegment::Index(self.index),
+        }
+    }
+}
+
+/// Checks that `bytes` is one well-formed value, with the same rules as [`Decoder`].
+/// Strings and byte strings are checked without copying them.
+/// The only allocation is the stack of open lists, one entry per level of nesting.
+///
+/// # Example
+/// ```
+/// use jtoo::{validate, ErrorReason};
+/// assert_eq!(validate(br#"[1,"a\22",[B0a,D2024-02-03]]"#), Ok(()));
+/// assert_eq!(
+///     validate(b"[1,1234]").unwrap_err().reason,
+///     ErrorReason::IncorrectDigitGrouping
+/// );
+/// ```
+///
+/// # Errors
+/// Returns `Err` when `bytes` is not a well-formed value.
+pub fn validate(bytes: &[u8]) -> Result<(), DecodeError> {
+    validate_with_options(bytes, DecoderOptions::default())
+}
+
+/// Like [`validate`], and also checks the limits in `options`.
+///
+/// # Errors
+/// Returns `Err` when `bytes` is not a well-formed value, or exceeds a limit.
+pub fn validate_with_options(bytes: &[u8], options: DecoderOptions) -> Result<(), DecodeError> {
+    let mut decoder = Decoder::with_options(bytes, options)?;
+    decoder.skip_value()?;
+    decoder.close()
+}
+
+/// An iterator over newline-separated records, from [`Decoder::records`].
+#[derive(Debug)]
+pub struct Records<'d, 'a, T> {
+    decoder: &'d mut Decoder<'a>,
+    phantom: PhantomData<fn() -> T>,
+}
+impl<'a, T: Decode<'a>> Iterator for Records<'_, 'a, T> {
+    type Item = Result<T, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.decoder.bytes;
+        if record.is_empty() {
+            return None;
+        }
+        self.decoder.in_records = true;
+        let result = T::decode_using(self.decoder).and_then(|value| {
+            if !self.decoder.lists.is_empty() {
+                return Err(self.decoder.err(ErrorReason::ListCloseNotConsumed));
+            }
+            if self.decoder.bytes.len() == record.len() {
+                return Err(self.decoder.err(ErrorReason::DataNotConsumed));
+            }
+            Ok(value)
+        });
+        self.decoder.in_records = false;
+        if result.is_err() {
+            self.decoder.skip_record(record);
+        }
+        Some(result)
+    }
+}
+
+/// Finds the `\n` that ends a value, for [`Decoder::records`] and [`crate::RecordReader`].
+/// Keeps its state between calls, so the value can arrive in pieces.
+/// With [`DecoderOptions::whitespace_and_comments`], it skips comments
+/// and lines with only whitespace and comments.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ValueFraming {
+    whitespace_and_comments: bool,
+    state: FramingState,
+    /// A byte that is not whitespace or a comment was scanned.
+    has_value: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FramingState {
+    Outside,
+    InString,
+    InComment,
+}
+
+impl ValueFraming {
+    pub(crate) fn new(options: &DecoderOptions) -> Self {
+        Self {
+            whitespace_and_comments: options.whitespace_and_comments,
+            state: FramingState::Outside,
+            has_value: false,
+        }
+    }
+
+    /// Returns the position of the `\n` in `bytes` that ends the value.
+    /// Each call continues where the bytes of the previous call ended.
+    /// The state stays before the returned `\n`, so scanning from it again returns it again.
+    pub(crate) fn find_end(&mut self, bytes: &[u8]) -> Option<usize> {
+        for (n, &b) in bytes.iter().enumerate() {
+            match self.state {
+                FramingState::InString => {
+                    if b == b'"' {
+                        self.state = FramingState::Outside;
+                    }
+                    continue;
+                }
+                FramingState::InComment if b != b'\n' => continue,
+                FramingState::InComment | FramingState::Outside => {
+                    self.state = FramingState::Outside;
+                }
+            }
+            match b {
+                b'\n' if self.has_value || !self.whitespace_and_comments => return Some(n),
+                b'\n' | b' ' | b'\t' | b'\r' if self.whitespace_and_comments => {}
+                b'#' if self.whitespace_and_comments => self.state = FramingState::InComment,
+                _ => {
+                    if b == b'"' {
+                        self.state = FramingState::InString;
+                    }
+                    self.has_value = true;
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns true when the scanned bytes are only whitespace and comments.
+    pub(crate) fn is_blank(self) -> bool {
+        !self.has_value
+    }
+}
+
+/// An iterator over the items of a list, from [`Decoder::list`].
+#[derive(Debug)]
+pub struct ListItems<'d, 'a, T> {
+    decoder: &'d mut Decoder<'a>,
+    opened: bool,
+    done: bool,
+    phantom: PhantomData<fn() -> T>,
+}
+impl<'a, T: Decode<'a>> Iterator for ListItems<'_, 'a, T> {
+    type Item = Result<T, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = if self.opened {
+            self.next_item()
+        } else {
+            self.opened = true;
+            self.decoder
+                .consume_list_open()
+                .and_then(|()| self.next_item())
+        };
+        match result {
+            Ok(Some(value)) => Some(Ok(value)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            // Each item takes at least one byte and a separator.
+            (0, Some(self.decoder.bytes.len().div_ceil(2)))
+        }
+    }
+}
+impl<'a, T: Decode<'a>> ListItems<'_, 'a, T> {
+    fn next_item(&mut self) -> Result<Option<T>, DecodeError> {
+        if self.decoder.has_another_list_item() {
+            T::decode_using(self.decoder).map(Some)
+        } else {
+            self.decoder.consume_list_close().map(|()| None)
+        }
+    }
+}
+
+/// Limits for decoding untrusted input.
+/// The defaults are unlimited.
+///
+/// # Example
+/// ```
+/// use jtoo::{Decoder, DecoderOptions, ErrorReason};
+/// let options = DecoderOptions {
+///     max_depth: 2,
+///     ..DecoderOptions::default()
+/// };
+/// let mut decoder = Decoder::with_options(b"[[[]]]", options).unwrap();
+/// decoder.consume_list_open().unwrap();
+/// decoder.consume_list_open().unwrap();
+/// assert_eq!(
+///     decoder.consume_list_open().unwrap_err().reason,
+///     ErrorReason::ListTooDeep
+/// );
+/// ```
+#[allow(clippy::module_name_repetitions)]
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DecoderOptions {
+    /// The maximum number of lists open at once.
+    pub max_depth: usize,
+    /// The maximum length of a decoded string, in UTF-8 bytes.
+    pub max_string_len: usize,
+    /// The maximum length of a decoded byte string, in bytes.
+    pub max_byte_string_len: usize,
+    /// The maximum number of values, counting lists and the items in them.
+    pub max_items: usize,
+    /// The maximum length of the input, in bytes.
+    pub max_input_len: usize,
+    /// The maximum number of warnings recorded in lenient mode.
+    pub max_warnings: usize,
+    /// Accept uppercase hex in byte strings, integers longer than three digits without
+    /// `_` separators, zero timezone offsets like `+00`, and a trailing `\n`.
+    /// Each deviation is recorded in [`Decoder::warnings`].
+    pub lenient: bool,
+    /// Accept spaces, tabs, newlines, and `#` comments before and after values,
+    /// like the output of [`crate::Encoder::pretty`].
+    /// This is an extension for hand-edited files, not part of JTOO.
+    /// With [`Decoder::records`] and [`crate::RecordReader`], `\n` still ends each value,
+    /// so each value must be on one line.
+    /// Lines with only whitespace and comments are skipped.
+    pub whitespace_and_comments: bool,
+}
+impl DecoderOptions {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_string_len: usize::MAX,
+            max_byte_string_len: usize::MAX,
+            max_items: usize::MAX,
+            max_input_len: usize::MAX,
+            max_warnings: usize::MAX,
+            lenient: false,
+            whitespace_and_comments: false,
+        }
+    }
+}
+impl Default for DecoderOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Marks bytes that are not digits in [`DIGIT_VALUES`].
+const NOT_DIGIT: u8 = 0xff;
+/// Set in [`DIGIT_VALUES`] for the uppercase hex digits `A`-`F`.
+const UPPERCASE_HEX: u8 = 0x10;
+/// The value of each byte as a hex digit, so decimal digits are the values below 10.
+const DIGIT_VALUES: [u8; 256] = {
+    let mut table = [NOT_DIGIT; 256];
+    let mut d = 0u8;
+    while d < 10 {
+        table[(b'0' + d) as usize] = d;
+        d += 1;
+    }
+    let mut d = 0u8;
+    while d < 6 {
+        table[(b'a' + d) as usize] = 10 + d;
+        table[(b'A' + d) as usize] = UPPERCASE_HEX | (10 + d);
+        d += 1;
+    }
+    table
+};
+
+fn digit_value(b: u8) -> Option<u8> {
+    let d = DIGIT_VALUES[usize::from(b)];
+    (d < 10).then_some(d)
+}
+
+/// A line and column in the input, so errors count lines from there instead of from the start.
+#[derive(Copy, Clone, Debug)]
+struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+impl Position {
+    const START: Position = Position {
+        offset: 0,
+        line: 1,
+        column: 1,
+    };
+}
+
 #[derive(Debug)]
 pub struct Decoder<'a> {
+    input: &'a [u8],
     bytes: &'a [u8],
     debug_bytes: &'a [u8],
-    list_depth: usize,
+    lists: Vec<ListLevel>,
+    options: DecoderOptions,
+    item_count: usize,
+    warnings: Vec<DecodeError>,
+    /// Top-level values may end with a `\n`, for [`Decoder::records`].
+    in_records: bool,
+    /// A position found by an earlier error.
+    /// Keeps lenient mode and [`Decoder::records`] linear when they report many errors.
+    position: Position,
 }
 impl<'a> Decoder<'a> {
     #[must_use]
     pub const fn new(bytes: &'a [u8]) -> Self {
         Self {
+            input: bytes,
             bytes,
             debug_bytes: bytes,
-            list_depth: 0,
+            lists: Vec::new(),
+            options: DecoderOptions::new(),
+            item_count: 0,
+            warnings: Vec::new(),
+            in_records: false,
+            position: Position::START,
+        }
+    }
+
+    /// # Errors
+    /// Returns `Err` when `bytes` is longer than `options.max_input_len`.
+    pub fn with_options(bytes: &'a [u8], options: DecoderOptions) -> Result<Self, DecodeError> {
+        let mut decoder = Self {
+            options,
+            ..Self::new(bytes)
+        };
+        if options.max_input_len < bytes.len() {
+            return Err(decoder.err(ErrorReason::InputTooLarge));
+        }
+        decoder.skip_whitespace_and_comments(true);
+        Ok(decoder)
+    }
+
+    /// Labels the current list item with a field name.
+    /// Errors in the item show `.name` in their path instead of `[index]`.
+    /// The label is cleared when the list moves to the next item.
+    /// Does nothing outside of a list.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::Decoder;
+    /// let mut decoder = Decoder::new(br#"[["a",T]]"#);
+    /// decoder.consume_list_open().unwrap();
+    /// decoder.consume_list_open().unwrap();
+    /// decoder.set_field("name");
+    /// decoder.consume_string().unwrap();
+    /// decoder.set_field("price");
+    /// let err = decoder.consume_integer().unwrap_err();
+    /// assert_eq!(err.path_string(), "[0].price");
+    /// ```
+    pub fn set_field(&mut self, name: &'static str) {
+        if let Some(level) = self.lists.last_mut() {
+            level.field = Some(name);
+        }
+    }
+
+    /// The deviations accepted in lenient mode, in input order.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::{Decoder, DecoderOptions, ErrorReason};
+    /// let options = DecoderOptions {
+    ///     lenient: true,
+    ///     ..DecoderOptions::default()
+    /// };
+    /// let mut decoder = Decoder::with_options(b"12345\n", options).unwrap();
+    /// assert_eq!(decoder.consume_integer(), Ok(12_345));
+    /// let reasons: Vec<ErrorReason> = decoder.warnings().iter().map(|w| w.reason).collect();
+    /// assert_eq!(
+    ///     reasons,
+    ///     [ErrorReason::IncorrectDigitGrouping, ErrorReason::DataNotConsumed]
+    /// );
+    /// decoder.close().unwrap();
+    /// ```
+    #[must_use]
+    pub fn warnings(&self) -> &[DecodeError] {
+        &self.warnings
+    }
+
+    /// Fails in strict mode. Records a warning in lenient mode.
+    fn deviation(&mut self, reason: ErrorReason) -> Result<(), DecodeError> {
+        if !self.options.lenient {
+            return Err(self.err(reason));
+        }
+        if self.warnings.len() == self.options.max_warnings {
+            return Err(self.err(ErrorReason::TooManyWarnings));
         }
+        let e = self.err(reason);
+        self.position = Position {
+            offset: e.offset,
+            line: e.line,
+            column: e.column,
+        };
+        self.warnings.push(e);
+        Ok(())
+    }
+
+    /// Decodes the rest of the input as a stream of top-level values, each followed by `\n`.
+    /// The last newline is optional.
+    /// After an error, the iterator skips to the next record.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::{Decoder, ErrorReason};
+    /// let mut decoder = Decoder::new(b"1\nx\n3\n");
+    /// let records: Vec<Result<i64, ErrorReason>> = decoder
+    ///     .records::<i64>()
+    ///     .map(|result| result.map_err(|e| e.reason))
+    ///     .collect();
+    /// assert_eq!(records, [Ok(1), Err(ErrorReason::ExpectedInteger), Ok(3)]);
+    /// decoder.close().unwrap();
+    /// ```
+    pub fn records<T: Decode<'a>>(&mut self) -> Records<'_, 'a, T> {
+        Records {
+            decoder: self,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Decodes a list of `T`, consuming the open and close brackets.
+    /// The iterator ends after the first error.
+    /// Errors in items have the item index at the end of their path.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::{Decoder, PathSegment};
+    /// let mut decoder = Decoder::new(b"[1,2,3]");
+    /// let values: Result<Vec<i64>, _> = decoder.list().collect();
+    /// assert_eq!(values, Ok(vec![1, 2, 3]));
+    /// decoder.close().unwrap();
+    ///
+    /// let mut decoder = Decoder::new(b"[1,T]");
+    /// let err = decoder.list::<i64>().find_map(Result::err).unwrap();
+    /// assert_eq!(err.path, [PathSegment::Index(1)]);
+    /// ```
+    pub fn list<T: Decode<'a>>(&mut self) -> ListItems<'_, 'a, T> {
+        ListItems {
+            decoder: self,
+            opened: false,
+            done: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Skips past the next `\n` that is not in a string, or to the end of the input.
+    fn skip_record(&mut self, record: &'a [u8]) {
+        let end = ValueFraming::new(&self.options).find_end(record);
+        self.bytes = end.map_or(&[], |n| &record[n + 1..]);
+        self.debug_bytes = self.bytes;
+        self.lists.clear();
+        self.skip_whitespace_and_comments(true);
+        self.position = self.position_of(self.input.len() - self.bytes.len());
     }
 
     /// # Errors
     /// Returns `Err` if the decoder has unconsumed data.
     pub fn close(self) -> Result<(), DecodeError> {
-        if self.list_depth != 0 {
+        if !self.lists.is_empty() {
             return Err(self.err(ErrorReason::ListCloseNotConsumed));
         }
         if !self.bytes.is_empty() {
@@ -288,18 +1017,260 @@ impl<'a> Decoder<'a> {
     }
 
     fn err(&self, reason: ErrorReason) -> DecodeError {
-        let debug_bytes = self
-            .debug_bytes
-            .iter()
-            .take(30)
-            .copied()
-            .collect::<Vec<_>>();
+        self.err_at(self.debug_bytes, reason)
+    }
+
+    /// `bytes` must be a suffix of the input.
+    fn err_at(&self, bytes: &[u8], reason: ErrorReason) -> DecodeError {
+        let debug_bytes = bytes.iter().take(30).copied().collect::<Vec<_>>();
+        let position = self.position_of(self.input.len() - bytes.len());
         DecodeError {
             reason,
             debug_bytes,
+            offset: position.offset,
+            line: position.line,
+            column: position.column,
+            path: self.lists.iter().copied().map(ListLevel::segment).collect(),
+            context: Vec::new(),
+        }
+    }
+
+    /// Counts the lines and columns up to `offset`, from `self.position` when it is before it.
+    #[allow(clippy::naive_bytecount)]
+    fn position_of(&self, offset: usize) -> Position {
+        let from = if self.position.offset <= offset {
+            self.position
+        } else {
+            Position::START
+        };
+        let between = &self.input[from.offset..offset];
+        match between.iter().rposition(|b| *b == b'\n') {
+            Some(n) => Position {
+                offset,
+                line: from.line + between.iter().filter(|b| **b == b'\n').count(),
+                column: between.len() - n,
+            },
+            None => Position {
+                offset,
+                line: from.line,
+                column: from.column + between.len(),
+            },
+        }
+    }
+
+    /// Consumes a value with `consume` and then converts it with `convert`.
+    /// When `convert` fails, the error points at the consumed value.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::{Decoder, ErrorReason};
+    /// let mut decoder = Decoder::new(b"1_000");
+    /// let err = decoder
+    ///     .consume_map(Decoder::consume_integer, |n| {
+    ///         u8::try_from(n).map_err(|_| ErrorReason::IntegerTooLarge)
+    ///     })
+    ///     .unwrap_err();
+    /// assert_eq!(err.reason, ErrorReason::IntegerTooLarge);
+    /// assert_eq!(err.debug_bytes, b"1_000");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `Err` when `consume` or `convert` fails.
+    pub fn consume_map<T, U>(
+        &mut self,
+        consume: impl FnOnce(&mut Self) -> Result<T, DecodeError>,
+        convert: impl FnOnce(T) -> Result<U, ErrorReason>,
+    ) -> Result<U, DecodeError> {
+        let item_bytes = self.bytes;
+        let depth = self.lists.len();
+        let item_level = self.lists.last().copied();
+        let value = consume(self)?;
+        convert(value).map_err(|reason| {
+            let mut e = self.err_at(item_bytes, reason);
+            // `consume` may have moved the list to the next item.
+            if let (true, Some(level), Some(segment)) =
+                (self.lists.len() == depth, item_level, e.path.last_mut())
+            {
+                *segment = level.segment();
+            }
+            e
+        })
+    }
+
+    /// Returns the type of the next value without consuming it.
+    /// Returns `None` when the next byte cannot start a value.
+    /// The value may still be malformed.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::{Decoder, ValueKind};
+    /// let mut decoder = Decoder::new(b"[T,T12,1.5]");
+    /// assert_eq!(decoder.peek_kind(), Some(ValueKind::List));
+    /// decoder.consume_list_open().unwrap();
+    /// assert_eq!(decoder.peek_kind(), Some(ValueKind::Bool));
+    /// decoder.consume_bool().unwrap();
+    /// assert_eq!(decoder.peek_kind(), Some(ValueKind::DateTime));
+    /// decoder.consume_date_time_tz_offset().unwrap();
+    /// assert_eq!(decoder.peek_kind(), Some(ValueKind::Decimal));
+    /// ```
+    #[must_use]
+    pub fn peek_kind(&self) -> Option<ValueKind> {
+        let kind = match self.bytes {
+            [] => ValueKind::End,
+            [b'T', b, ..] if b.is_ascii_digit() => ValueKind::DateTime,
+            [b'T' | b'F', ..] => ValueKind::Bool,
+            [b'D', ..] => ValueKind::DateTime,
+            [b'S', ..] => ValueKind::Timestamp,
+            [b'"', ..] => ValueKind::String,
+            [b'B', ..] => ValueKind::ByteString,
+            [b'[', ..] => ValueKind::List,
+            [b']', ..] => ValueKind::ListEnd,
+            [b'-' | b'0'..=b'9', rest @ ..] => {
+                match rest.iter().find(|b| !b.is_ascii_digit() && **b != b'_') {
+                    Some(b'.') => ValueKind::Decimal,
+                    _ => ValueKind::Integer,
+                }
+            }
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Consumes the next value, including everything in it when it is a list.
+    /// The value is checked with the same rules as the `consume_` methods,
+    /// without copying strings or byte strings.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::Decoder;
+    /// let mut decoder = Decoder::new(br#"[[1,"a",[]],2]"#);
+    /// decoder.consume_list_open().unwrap();
+    /// decoder.skip_value().unwrap();
+    /// assert_eq!(decoder.consume_integer(), Ok(2));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `Err` when the next value is malformed, or there is no next value.
+    pub fn skip_value(&mut self) -> Result<(), DecodeError> {
+        // Counts open lists instead of recursing, so deep nesting cannot overflow the stack.
+        let mut depth = 0_usize;
+        loop {
+            if depth != 0 && !self.has_another_list_item() {
+                self.consume_list_close()?;
+                depth -= 1;
+            } else {
+                match self.peek_kind() {
+                    Some(ValueKind::Bool) => self.consume_bool().map(|_| ()),
+                    Some(ValueKind::Integer) => {
+                        if self.bytes.first() == Some(&b'-') {
+                            self.consume_i128().map(|_| ())
+                        } else {
+                            self.consume_u128().map(|_| ())
+                        }
+                    }
+                    Some(ValueKind::Decimal) => self.consume_decimal().map(|_| ()),
+                    Some(ValueKind::String) => self.skip_str(),
+                    Some(ValueKind::ByteString) => self.consume_byte_string_with(|_| ()),
+                    Some(ValueKind::DateTime) => self.consume_date_time_tz_offset().map(|_| ()),
+                    Some(ValueKind::Timestamp) => self.consume_timestamp().map(|_| ()),
+                    Some(ValueKind::List) => {
+                        depth += 1;
+                        self.consume_list_open()
+                    }
+                    Some(ValueKind::ListEnd | ValueKind::End) | None => {
+                        Err(self.err(ErrorReason::ExpectedValue))
+                    }
+                }?;
+            }
+            if depth == 0 {
+                return Ok(());
+            }
         }
     }
 
+    pub(crate) fn options(&self) -> DecoderOptions {
+        self.options
+    }
+
+    /// The number of input bytes consumed.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.input.len() - self.bytes.len()
+    }
+
+    /// Saves the position, so you can try decoding a value one way
+    /// and [`Decoder::rewind`] to try another way.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::Decoder;
+    /// let mut decoder = Decoder::new(br#"["a",2]"#);
+    /// decoder.consume_list_open().unwrap();
+    /// let mut values = Vec::new();
+    /// while decoder.has_another_list_item() {
+    ///     let checkpoint = decoder.checkpoint();
+    ///     let value = match decoder.consume_integer() {
+    ///         Ok(n) => n.to_string(),
+    ///         Err(_) => {
+    ///             decoder.rewind(&checkpoint);
+    ///             decoder.consume_string().unwrap()
+    ///         }
+    ///     };
+    ///     values.push(value);
+    /// }
+    /// decoder.consume_list_close().unwrap();
+    /// decoder.close().unwrap();
+    /// assert_eq!(values, ["a", "2"]);
+    /// ```
+    #[must_use]
+    pub fn checkpoint(&self) -> Checkpoint<'a> {
+        Checkpoint {
+            input: self.input,
+            bytes: self.bytes,
+            debug_bytes: self.debug_bytes,
+            lists: self.lists.clone(),
+            item_count: self.item_count,
+            warning_count: self.warnings.len(),
+            in_records: self.in_records,
+        }
+    }
+
+    /// Restores the position saved in `checkpoint`, including open lists, field names,
+    /// and limit counts, and drops warnings recorded after it.
+    ///
+    /// # Panics
+    /// Panics when `checkpoint` is from a decoder with different input.
+    pub fn rewind(&mut self, checkpoint: &Checkpoint<'a>) {
+        assert!(
+            core::ptr::eq(self.input, checkpoint.input),
+            "checkpoint is from a different decoder"
+        );
+        self.bytes = checkpoint.bytes;
+        self.debug_bytes = checkpoint.debug_bytes;
+        self.lists.clone_from(&checkpoint.lists);
+        self.item_count = checkpoint.item_count;
+        self.warnings.truncate(checkpoint.warning_count);
+        self.in_records = checkpoint.in_records;
+    }
+
+    /// Returns a decoder for one item of a list that starts at `offset` in `input`.
+    /// Errors have `index` in their path.
+    pub(crate) fn at_list_item(
+        input: &'a [u8],
+        offset: usize,
+        index: usize,
+        options: DecoderOptions,
+    ) -> Self {
+        let mut decoder = Self {
+            options,
+            ..Self::new(input)
+        };
+        decoder.bytes = &input[offset..];
+        decoder.debug_bytes = decoder.bytes;
+        decoder.lists.push(ListLevel { index, field: None });
+        decoder
+    }
+
     /// # Errors
     /// Returns `Err` when the next item in the buffer is not a bool, or the buffer is empty.
     pub fn consume_bool(&mut self) -> Result<bool, DecodeError> {
@@ -326,34 +1297,76 @@ impl<'a> Decoder<'a> {
         self.bytes = &self.bytes[n..];
     }
 
-    pub fn consume_byte_string(&mut self) -> Result<Vec<u8>, DecodeError> {
-        self.consume_exact(b'B')
-            .ok_or_else(|| self.err(ErrorReason::ExpectedByteString))?;
-        let mut result = Vec::new();
-        loop {
-            let d0 = match self.consume_byte() {
-                Some(b) if (b'0'..=b'9').contains(&b) => b - b'0',
-                Some(b) if (b'a'..=b'f').contains(&b) => 10 + b - b'a',
-                Some(b) if (b'A'..=b'F').contains(&b) => {
-                    return Err(self.err(ErrorReason::UppercaseHexNotAllowedInByteString))
+    /// Skips spaces, tabs, and `#` comments when [`DecoderOptions::whitespace_and_comments`] is set.
+    /// Skips newlines too, when `newlines` is true.
+    fn skip_whitespace_and_comments(&mut self, newlines: bool) {
+        if !self.options.whitespace_and_comments {
+            return;
+        }
+        let mut n = 0;
+        while let Some(&b) = self.bytes.get(n) {
+            match b {
+                b' ' | b'\t' | b'\r' => n += 1,
+                b'\n' if newlines => n += 1,
+                b'#' => {
+                    n += self.bytes[n..]
+                        .iter()
+                        .position(|&b| b == b'\n')
+                        .unwrap_or(self.bytes.len() - n);
                 }
                 _ => break,
-            };
-            let d1 = match self.consume_byte() {
-                Some(b) if (b'0'..=b'9').contains(&b) => b - b'0',
-                Some(b) if (b'a'..=b'f').contains(&b) => 10 + b - b'a',
-                Some(b) if (b'A'..=b'F').contains(&b) => {
-                    return Err(self.err(ErrorReason::UppercaseHexNotAllowedInByteString))
-                }
-                _ => return Err(self.err(ErrorReason::MalformedByteString)),
-            };
-            let b = (d0 << 4) | d1;
-            result.push(b);
+            }
         }
-        self.close_item(ErrorReason::MalformedByteString)?;
+        self.consume_bytes(n);
+        self.debug_bytes = self.bytes;
+    }
+
+    /// Returns true when the next byte ends a value, like `,` or `]`.
+    fn at_value_end(&self) -> bool {
+        match self.bytes.first() {
+            None | Some(b',' | b']') => true,
+            Some(b' ' | b'\t' | b'\r' | b'\n' | b'#') => self.options.whitespace_and_comments,
+            _ => false,
+        }
+    }
+
+    /// # Errors
+    /// Returns `Err` when the next item in the buffer is not a byte string, or the buffer is empty.
+    pub fn consume_byte_string(&mut self) -> Result<Vec<u8>, DecodeError> {
+        let mut result = Vec::new();
+        self.consume_byte_string_with(|b| result.push(b))?;
         Ok(result)
     }
 
+    /// Consumes a byte string and calls `push` with each byte.
+    fn consume_byte_string_with(&mut self, mut push: impl FnMut(u8)) -> Result<(), DecodeError> {
+        self.consume_exact(b'B')
+            .ok_or_else(|| self.err(ErrorReason::ExpectedByteString))?;
+        let digits = self.bytes;
+        let digit_count = digits
+            .iter()
+            .take_while(|b| DIGIT_VALUES[usize::from(**b)] != NOT_DIGIT)
+            .count();
+        let digits = &digits[..digit_count];
+        // Uppercase digits are a deviation, reported once per byte string.
+        if digits.iter().any(u8::is_ascii_uppercase) {
+            self.deviation(ErrorReason::UppercaseHexNotAllowedInByteString)?;
+        }
+        if self.options.max_byte_string_len < digit_count / 2 {
+            return Err(self.err(ErrorReason::ByteStringTooLong));
+        }
+        if digit_count % 2 != 0 {
+            return Err(self.err(ErrorReason::MalformedByteString));
+        }
+        for pair in digits.chunks_exact(2) {
+            let d0 = DIGIT_VALUES[usize::from(pair[0])] & 0x0f;
+            let d1 = DIGIT_VALUES[usize::from(pair[1])] & 0x0f;
+            push((d0 << 4) | d1);
+        }
+        self.consume_bytes(digit_count);
+        self.close_item(ErrorReason::MalformedByteString)
+    }
+
     fn consume_exact(&mut self, c: u8) -> Option<()> {
         if self.bytes.first() == Some(&c) {
             self.bytes = &self.bytes[1..];
@@ -363,110 +1376,314 @@ impl<'a> Decoder<'a> {
         }
     }
 
-    /// # Errors
-    /// Returns `Err` when the next item in the buffer is not an integer, or the buffer is empty.
-    pub fn consume_integer(&mut self) -> Result<i64, DecodeError> {
-        let sign = if self.consume_exact(b'-').is_some() {
-            -1
-        } else {
-            1
-        };
+    /// Consumes an optional `-` and grouped digits, without consuming the byte after the digits.
+    fn consume_integer_digits(&mut self) -> Result<(bool, u128), DecodeError> {
+        let is_negative = self.consume_exact(b'-').is_some();
         let mut seen_underscore = false;
+        let mut digit_count = 0usize;
         let mut group_digit_count = 0u16;
-        let mut value = 0i64;
-        while let Some(b) = self.consume_byte() {
-            match b {
-                b'0'..=b'9' => {
-                    group_digit_count += 1;
-                    let d = i64::from(b - b'0');
-                    value = value
-                        .checked_mul(10)
-                        .ok_or_else(|| self.err(ErrorReason::IntegerTooLarge))?;
-                    value = value
-                        .checked_add(sign * d)
-                        .ok_or_else(|| self.err(ErrorReason::IntegerTooLarge))?;
-                    if value == 0 && 1 < group_digit_count {
-                        return Err(self.err(ErrorReason::ExpectedSingleZero));
-                    }
+        let mut value = 0u128;
+        let mut n = 0;
+        while let Some(&b) = self.bytes.get(n) {
+            if let Some(d) = digit_value(b) {
+                if digit_count == 1 && value == 0 {
+                    return Err(self.err(ErrorReason::ExpectedSingleZero));
                 }
-                b'_' => {
-                    if seen_underscore {
-                        if group_digit_count != 3 {
-                            return Err(self.err(ErrorReason::IncorrectDigitGrouping));
-                        }
-                    } else if value == 0 {
+                value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(u128::from(d)))
+                    .ok_or_else(|| self.err(ErrorReason::IntegerTooLarge))?;
+                digit_count += 1;
+                group_digit_count += 1;
+            } else if b == b'_' {
+                if seen_underscore {
+                    if group_digit_count != 3 {
                         return Err(self.err(ErrorReason::IncorrectDigitGrouping));
                     }
-                    seen_underscore = true;
-                    group_digit_count = 0;
+                } else if value == 0 {
+                    return Err(self.err(ErrorReason::IncorrectDigitGrouping));
                 }
-                _ => break,
+                seen_underscore = true;
+                group_digit_count = 0;
+            } else {
+                break;
             }
+            n += 1;
         }
-        if value == 0 && group_digit_count == 0 {
+        self.consume_bytes(n);
+        if digit_count == 0 {
             return Err(self.err(ErrorReason::ExpectedInteger));
         }
         if seen_underscore && group_digit_count != 3 {
             return Err(self.err(ErrorReason::IncorrectDigitGrouping));
         }
         if !seen_underscore && 3 < group_digit_count {
-            return Err(self.err(ErrorReason::IncorrectDigitGrouping));
+            self.deviation(ErrorReason::IncorrectDigitGrouping)?;
+        }
+        Ok((is_negative, value))
+    }
+
+    fn consume_signed<T: TryFrom<i128>>(&mut self) -> Result<T, DecodeError> {
+        let (is_negative, magnitude) = self.consume_integer_digits()?;
+        if is_negative && magnitude == 0 {
+            return Err(self.err(ErrorReason::NegativeZero));
         }
-        if sign == -1 && value == 0 {
+        let value = if is_negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        };
+        let value = value
+            .and_then(|v| T::try_from(v).ok())
+            .ok_or_else(|| self.err(ErrorReason::IntegerTooLarge))?;
+        self.close_item(ErrorReason::MalformedInteger)?;
+        Ok(value)
+    }
+
+    fn consume_unsigned<T: TryFrom<u128>>(&mut self) -> Result<T, DecodeError> {
+        let (is_negative, magnitude) = self.consume_integer_digits()?;
+        if is_negative && magnitude == 0 {
             return Err(self.err(ErrorReason::NegativeZero));
         }
+        if is_negative {
+            return Err(self.err(ErrorReason::NegativeNotAllowed));
+        }
+        let value = T::try_from(magnitude).map_err(|_| self.err(ErrorReason::IntegerTooLarge))?;
         self.close_item(ErrorReason::MalformedInteger)?;
         Ok(value)
     }
 
+    /// `1_234`, `-5`
+    ///
+    /// # Errors
+    /// Returns `Err` when the next item in the buffer is not an integer, or the buffer is empty.
+    pub fn consume_integer(&mut self) -> Result<i64, DecodeError> {
+        self.consume_signed()
+    }
+
+    /// # Errors
+    /// Returns `Err` when the next item in the buffer is not a non-negative integer that fits in `u64`,
+    /// or the buffer is empty.
+    pub fn consume_u64(&mut self) -> Result<u64, DecodeError> {
+        self.consume_unsigned()
+    }
+
+    /// # Errors
+    /// Returns `Err` when the next item in the buffer is not an integer that fits in `i128`,
+    /// or the buffer is empty.
+    pub fn consume_i128(&mut self) -> Result<i128, DecodeError> {
+        self.consume_signed()
+    }
+
+    /// # Errors
+    /// Returns `Err` when the next item in the buffer is not a non-negative integer that fits in `u128`,
+    /// or the buffer is empty.
+    pub fn consume_u128(&mut self) -> Result<u128, DecodeError> {
+        self.consume_unsigned()
+    }
+
+    /// Consumes the digits after a decimal point and appends them to `mantissa`.
+    /// Returns the number of digits.
+    fn consume_fraction_digits(
+        &mut self,
+        mantissa: &mut u128,
+        too_large: ErrorReason,
+        malformed: ErrorReason,
+    ) -> Result<usize, DecodeError> {
+        let mut digit_count = 0usize;
+        let mut group_digit_count = 0u16;
+        let mut n = 0;
+        while let Some(&b) = self.bytes.get(n) {
+            if let Some(d) = digit_value(b) {
+                if group_digit_count == 3 {
+                    return Err(self.err(ErrorReason::IncorrectDigitGrouping));
+                }
+                *mantissa = mantissa
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(u128::from(d)))
+                    .ok_or_else(|| self.err(too_large))?;
+                digit_count += 1;
+                group_digit_count += 1;
+            } else if b == b'_' {
+                if group_digit_count != 3 {
+                    return Err(self.err(ErrorReason::IncorrectDigitGrouping));
+                }
+                group_digit_count = 0;
+            } else {
+                break;
+            }
+            n += 1;
+        }
+        self.consume_bytes(n);
+        if digit_count == 0 {
+            return Err(self.err(malformed));
+        }
+        if group_digit_count == 0 {
+            return Err(self.err(ErrorReason::IncorrectDigitGrouping));
+        }
+        Ok(digit_count)
+    }
+
+    /// `1_234.567_8`, `-0.5`, `10.0`
+    ///
+    /// Returns the mantissa and base-10 exponent, so `1_234.567_8` is `(12_345_678, -4)`.
+    /// The exponent is the negated number of digits after the decimal point,
+    /// except that a single `0` after the point means exponent `0`, so `10.0` is `(10, 0)`.
+    ///
+    /// # Errors
+    /// Returns `Err` when the next item in the buffer is not a decimal, or the buffer is empty.
+    pub fn consume_decimal(&mut self) -> Result<(i64, i8), DecodeError> {
+        if !matches!(self.bytes.first(), Some(b'-' | b'0'..=b'9')) {
+            return Err(self.err(ErrorReason::ExpectedDecimal));
+        }
+        let (is_negative, mut mantissa) =
+            self.consume_integer_digits().map_err(|e| match e.reason {
+                ErrorReason::ExpectedInteger => self.err(ErrorReason::MalformedDecimal),
+                ErrorReason::IntegerTooLarge => self.err(ErrorReason::DecimalTooLarge),
+                _ => e,
+            })?;
+        if self.consume_exact(b'.').is_none() {
+            return Err(self.err(ErrorReason::ExpectedDecimal));
+        }
+        let is_single_zero = self.bytes.first() == Some(&b'0')
+            && !matches!(self.bytes.get(1), Some(b'0'..=b'9' | b'_'));
+        let exponent = if is_single_zero {
+            self.consume_bytes(1);
+            0
+        } else {
+            let digit_count = self.consume_fraction_digits(
+                &mut mantissa,
+                ErrorReason::DecimalTooLarge,
+                ErrorReason::MalformedDecimal,
+            )?;
+            isize::try_from(digit_count)
+                .ok()
+                .and_then(|n| i8::try_from(-n).ok())
+                .ok_or_else(|| self.err(ErrorReason::DecimalTooPrecise))?
+        };
+        if is_negative && mantissa == 0 {
+            return Err(self.err(ErrorReason::NegativeZero));
+        }
+        let mantissa = i128::try_from(mantissa)
+            .ok()
+            .map(|m| if is_negative { -m } else { m })
+            .and_then(|m| i64::try_from(m).ok())
+            .ok_or_else(|| self.err(ErrorReason::DecimalTooLarge))?;
+        self.close_item(ErrorReason::MalformedDecimal)?;
+        Ok((mantissa, exponent))
+    }
+
+    /// `S1_234`, `S1_234.500`, `S1_234.567_800`, `S1_234.567_890_100`, `S-0.500`
+    ///
+    /// # Errors
+    /// Returns `Err` when the next item in the buffer is not a timestamp, or the buffer is empty.
+    pub fn consume_timestamp(&mut self) -> Result<Timestamp, DecodeError> {
+        self.consume_exact(b'S')
+            .ok_or_else(|| self.err(ErrorReason::ExpectedTimestamp))?;
+        let (is_negative, mut value) =
+            self.consume_integer_digits().map_err(|e| match e.reason {
+                ErrorReason::ExpectedInteger => self.err(ErrorReason::MalformedTimestamp),
+                ErrorReason::IntegerTooLarge => self.err(ErrorReason::TimestampTooLarge),
+                _ => e,
+            })?;
+        let precision = if self.consume_exact(b'.').is_some() {
+            let digit_count = self.consume_fraction_digits(
+                &mut value,
+                ErrorReason::TimestampTooLarge,
+                ErrorReason::MalformedTimestamp,
+            )?;
+            match digit_count {
+                3 => TimestampPrecision::Milliseconds,
+                6 => TimestampPrecision::Microseconds,
+                9 => TimestampPrecision::Nanoseconds,
+                _ => return Err(self.err(ErrorReason::InvalidTimestampPrecision)),
+            }
+        } else {
+            TimestampPrecision::Seconds
+        };
+        if is_negative && value == 0 {
+            return Err(self.err(ErrorReason::NegativeZero));
+        }
+        let value = i128::try_from(value)
+            .ok()
+            .map(|v| if is_negative { -v } else { v })
+            .and_then(|v| i64::try_from(v).ok())
+            .ok_or_else(|| self.err(ErrorReason::TimestampTooLarge))?;
+        self.close_item(ErrorReason::MalformedTimestamp)?;
+        Ok(Timestamp { value, precision })
+    }
+
     /// # Errors
     /// Returns `Err` when the next item in the buffer is not an open list symbol `[`, or the buffer is empty.
     pub fn consume_list_open(&mut self) -> Result<(), DecodeError> {
+        if self.bytes.first() == Some(&b'[') && self.lists.len() == self.options.max_depth {
+            return Err(self.err(ErrorReason::ListTooDeep));
+        }
         self.consume_exact(b'[')
             .ok_or_else(|| self.err(ErrorReason::ExpectedList))?;
-        self.list_depth += 1;
+        self.lists.push(ListLevel {
+            index: 0,
+            field: None,
+        });
+        self.debug_bytes = self.bytes;
+        self.skip_whitespace_and_comments(!self.in_records);
         Ok(())
     }
 
     #[allow(clippy::match_same_arms)]
     fn close_item(&mut self, reason: ErrorReason) -> Result<(), DecodeError> {
-        if self.list_depth == 0 {
-            if !self.bytes.is_empty() {
-                return Err(self.err(reason));
-            }
-        } else {
+        if self.item_count == self.options.max_items {
+            return Err(self.err(ErrorReason::TooManyItems));
+        }
+        self.item_count += 1;
+        self.skip_whitespace_and_comments(!self.in_records);
+        if let Some(level) = self.lists.last_mut() {
             match self.bytes.first() {
                 Some(&b',') => {
-                    self.consume_byte();
+                    self.bytes = &self.bytes[1..];
+                    level.index += 1;
+                    level.field = None;
+                    self.skip_whitespace_and_comments(!self.in_records);
+                    // A list cannot end with a separator, like `[1,]`.
+                    if self.bytes.first() == Some(&b']') {
+                        self.debug_bytes = self.bytes;
+                        return Err(self.err(ErrorReason::ExpectedValue));
+                    }
                 }
                 Some(&b']') => {}
                 None => {} // Next call will try to consume list close and fail.
                 _ => return Err(self.err(ErrorReason::ExpectedListSeparator)),
             }
+        } else if self.in_records && self.bytes.first() == Some(&b'\n') {
+            self.consume_bytes(1);
+            self.skip_whitespace_and_comments(true);
+        } else if self.bytes == b"\n" && self.options.lenient {
+            self.debug_bytes = self.bytes;
+            self.deviation(ErrorReason::DataNotConsumed)?;
+            self.consume_bytes(1);
+        } else if !self.bytes.is_empty() {
+            return Err(self.err(reason));
         }
         self.debug_bytes = self.bytes;
         Ok(())
     }
 
     fn consume_date_digit(&mut self) -> Result<u8, DecodeError> {
-        match self.consume_byte() {
-            Some(b) if b.is_ascii_digit() => Ok(b - b'0'),
-            _ => Err(self.err(ErrorReason::MalformedDate)),
-        }
+        self.consume_byte()
+            .and_then(digit_value)
+            .ok_or_else(|| self.err(ErrorReason::MalformedDate))
     }
 
     fn consume_tz_offset_digit(&mut self) -> Result<u8, DecodeError> {
-        match self.consume_byte() {
-            Some(b) if b.is_ascii_digit() => Ok(b - b'0'),
-            _ => Err(self.err(ErrorReason::MalformedTimeZoneOffset)),
-        }
+        self.consume_byte()
+            .and_then(digit_value)
+            .ok_or_else(|| self.err(ErrorReason::MalformedTimeZoneOffset))
     }
 
     fn consume_time_digit(&mut self) -> Result<u8, DecodeError> {
-        match self.consume_byte() {
-            Some(b) if b.is_ascii_digit() => Ok(b - b'0'),
-            _ => Err(self.err(ErrorReason::MalformedTime)),
-        }
+        self.consume_byte()
+            .and_then(digit_value)
+            .ok_or_else(|| self.err(ErrorReason::MalformedTime))
     }
 
     pub fn has_another_list_item(&mut self) -> bool {
@@ -476,77 +1693,139 @@ impl<'a> Decoder<'a> {
     /// # Errors
     /// Returns `Err` when the next item in the buffer is not a close list symbol `]`, or the buffer is empty.
     pub fn consume_list_close(&mut self) -> Result<(), DecodeError> {
-        if self.list_depth == 0 {
+        if self.lists.is_empty() {
             return Err(self.err(ErrorReason::NotInList));
         }
         self.consume_exact(b']')
             .ok_or_else(|| self.err(ErrorReason::ExpectedListEnd))?;
+        self.lists.pop();
         self.close_item(ErrorReason::MalformedListEnd)?;
-        self.list_depth -= 1;
         Ok(())
     }
 
     /// # Errors
     /// Returns `Err` when the next item in the buffer is not a string, or the buffer is empty.
-    #[allow(clippy::missing_panics_doc)]
     pub fn consume_string(&mut self) -> Result<String, DecodeError> {
-        match self.bytes.first() {
-            Some(b'"') => {}
-            _ => return Err(self.err(ErrorReason::ExpectedString)),
-        }
-        let Some((len, _)) = self
-            .bytes
-            .iter()
-            .copied()
-            .enumerate()
-            .skip(1)
-            .find(|(_n, b)| b == &b'"')
-        else {
-            return Err(self.err(ErrorReason::UnclosedString));
-        };
-        let s = core::str::from_utf8(&self.bytes[1..len])
-            .map_err(|_e| self.err(ErrorReason::NotUtf8))?;
-        let mut value = String::with_capacity(len - 1);
-        let mut chars = s.chars();
-        while let Some(c) = chars.next() {
-            if c == '\\' {
-                let opt_c1 = chars.next();
-                let opt_c2 = chars.next();
-                let (Some(c1), Some(c2)) = (opt_c1, opt_c2) else {
-                    return Err(DecodeError {
-                        reason: ErrorReason::IncompleteEscapeSequence,
-                        debug_bytes: [Some('\\'), opt_c1, opt_c2, Some('"')]
-                            .iter()
-                            .flatten()
-                            .collect::<String>()
-                            .into_bytes(),
-                    });
-                };
-                let (Some(b1), Some(b2)) = (c1.to_digit(16), c2.to_digit(16)) else {
-                    return Err(DecodeError {
-                        reason: ErrorReason::InvalidEscapeSequence,
-                        debug_bytes: format!("\\{c1}{c2}").into_bytes(),
-                    });
-                };
-                let b = 16 * b1 + b2;
-                match b {
-                    0x00..=0x1f | 0x22 | 0x5c | 0x7f => value.push(char::from_u32(b).unwrap()),
-                    _ => {
-                        return Err(DecodeError {
-                            reason: ErrorReason::InvalidEscapeSequence,
-                            debug_bytes: format!("\\{c1}{c2}").into_bytes(),
-                        });
-                    }
-                }
+        self.consume_str().map(Cow::into_owned)
+    }
+
+    /// Borrows the string from the buffer when it contains no escape sequences.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::Decoder;
+    /// use std::borrow::Cow;
+    /// let mut decoder = Decoder::new(br#"["abc","a\22c"]"#);
+    /// decoder.consume_list_open().unwrap();
+    /// assert_eq!(decoder.consume_str(), Ok(Cow::Borrowed("abc")));
+    /// assert_eq!(decoder.consume_str(), Ok(Cow::Owned("a\"c".to_string())));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `Err` when the next item in the buffer is not a string, or the buffer is empty.
+    pub fn consume_str(&mut self) -> Result<Cow<'a, str>, DecodeError> {
+        let (s, len, has_escapes) = self.scan_string()?;
+        // Escapes only shorten a string, so count the decoded length before copying
+        // only when the raw length is over the limit.
+        if self.options.max_string_len < s.len() {
+            let decoded_len = if has_escapes {
+                self.unescape(s, |_| ())?
             } else {
-                value.push(c);
+                s.len()
+            };
+            if self.options.max_string_len < decoded_len {
+                return Err(self.err(ErrorReason::StringTooLong));
             }
         }
-        self.consume_bytes(len + 1);
+        let value = if has_escapes {
+            let mut value = String::with_capacity(s.len());
+            self.unescape(s, |part| value.push_str(part))?;
+            Cow::Owned(value)
+        } else {
+            Cow::Borrowed(s)
+        };
+        self.consume_bytes(len);
         self.close_item(ErrorReason::MalformedString)?;
         Ok(value)
     }
 
+    /// Consumes a string without copying it.
+    fn skip_str(&mut self) -> Result<(), DecodeError> {
+        let (s, len, has_escapes) = self.scan_string()?;
+        let decoded_len = if has_escapes {
+            self.unescape(s, |_| ())?
+        } else {
+            s.len()
+        };
+        if self.options.max_string_len < decoded_len {
+            return Err(self.err(ErrorReason::StringTooLong));
+        }
+        self.consume_bytes(len);
+        self.close_item(ErrorReason::MalformedString)
+    }
+
+    /// Returns the contents of the string at the start of the buffer,
+    /// the length of the string with its quotes, and whether it contains escape sequences.
+    fn scan_string(&self) -> Result<(&'a str, usize, bool), DecodeError> {
+        let bytes = self.bytes;
+        if bytes.first() != Some(&b'"') {
+            return Err(self.err(ErrorReason::ExpectedString));
+        }
+        let unclosed = || self.err(ErrorReason::UnclosedString);
+        let content = &bytes[1..];
+        // Stops at the first escape, to skip the search for escapes when there are none.
+        let n = content
+            .iter()
+            .position(|b| *b == b'"' || *b == b'\\')
+            .ok_or_else(unclosed)?;
+        let has_escapes = content[n] == b'\\';
+        let len = if has_escapes {
+            n + content[n..]
+                .iter()
+                .position(|b| *b == b'"')
+                .ok_or_else(unclosed)?
+        } else {
+            n
+        };
+        let s =
+            core::str::from_utf8(&bytes[1..=len]).map_err(|_e| self.err(ErrorReason::NotUtf8))?;
+        Ok((s, len + 2, has_escapes))
+    }
+
+    /// Calls `push` with the parts of `s`, the contents of the string at the start of the buffer,
+    /// after replacing escape sequences.
+    /// Returns the length of the result in UTF-8 bytes.
+    fn unescape(&self, s: &str, mut push: impl FnMut(&str)) -> Result<usize, DecodeError> {
+        let bytes = s.as_bytes();
+        let mut len = 0;
+        let mut start = 0;
+        while let Some(n) = bytes[start..].iter().position(|b| *b == b'\\') {
+            let n = start + n;
+            let escape_bytes = &self.bytes[1 + n..];
+            let (Some(&c1), Some(&c2)) = (bytes.get(n + 1), bytes.get(n + 2)) else {
+                return Err(self.err_at(escape_bytes, ErrorReason::IncompleteEscapeSequence));
+            };
+            let (d1, d2) = (DIGIT_VALUES[usize::from(c1)], DIGIT_VALUES[usize::from(c2)]);
+            if d1 == NOT_DIGIT || d2 == NOT_DIGIT {
+                return Err(self.err_at(escape_bytes, ErrorReason::InvalidEscapeSequence));
+            }
+            let b = ((d1 & 0x0f) << 4) | (d2 & 0x0f);
+            if !matches!(b, 0x00..=0x1f | 0x22 | 0x5c | 0x7f) {
+                return Err(self.err_at(escape_bytes, ErrorReason::InvalidEscapeSequence));
+            }
+            let mut buf = [0u8; 4];
+            let parts: [&str; 2] = [&s[start..n], char::from(b).encode_utf8(&mut buf)];
+            for part in parts {
+                len += part.len();
+                push(part);
+            }
+            start = n + 3;
+        }
+        len += s.len() - start;
+        push(&s[start..]);
+        Ok(len)
+    }
+
     fn consume_year(&mut self) -> Result<u16, DecodeError> {
         assert_eq!(self.consume_byte(), Some(b'D'));
         let d0 = u16::from(self.consume_date_digit()?);
@@ -571,7 +1850,7 @@ impl<'a> Decoder<'a> {
         Ok(mo)
     }
 
-    fn consume_week(&mut self) -> Result<u8, DecodeError> {
+    fn consume_week(&mut self, y: u16) -> Result<u8, DecodeError> {
         assert_eq!(self.consume_byte(), Some(b'-'));
         assert_eq!(self.consume_byte(), Some(b'W'));
         let d0 = self.consume_date_digit()?;
@@ -580,10 +1859,13 @@ impl<'a> Decoder<'a> {
         if !(1..=53).contains(&w) {
             return Err(self.err(ErrorReason::WeekOutOfRange));
         }
+        if weeks_in_year(y) < w {
+            return Err(self.err(ErrorReason::WeekNotInYear));
+        }
         Ok(w)
     }
 
-    fn consume_day(&mut self) -> Result<u8, DecodeError> {
+    fn consume_day(&mut self, y: u16, mo: u8) -> Result<u8, DecodeError> {
         assert_eq!(self.consume_byte(), Some(b'-'));
         let d0 = self.consume_date_digit()?;
         let d1 = self.consume_date_digit()?;
@@ -591,6 +1873,9 @@ impl<'a> Decoder<'a> {
         if !(1..=31).contains(&d) {
             return Err(self.err(ErrorReason::DayOutOfRange));
         }
+        if days_in_month(y, mo) < d {
+            return Err(self.err(ErrorReason::DayNotInMonth));
+        }
         Ok(d)
     }
 
@@ -613,7 +1898,7 @@ impl<'a> Decoder<'a> {
             return Ok(Date::Year { y });
         }
         let month_or_week = if self.bytes.get(1).copied() == Some(b'W') {
-            MonthOrWeek::Week(self.consume_week()?)
+            MonthOrWeek::Week(self.consume_week(y)?)
         } else {
             MonthOrWeek::Month(self.consume_month()?)
         };
@@ -625,7 +1910,7 @@ impl<'a> Decoder<'a> {
         }
         match month_or_week {
             MonthOrWeek::Month(mo) => {
-                let d = self.consume_day()?;
+                let d = self.consume_day(y, mo)?;
                 Ok(Date::YearMonthDay { y, mo, d })
             }
             MonthOrWeek::Week(w) => {
@@ -691,9 +1976,8 @@ impl<'a> Decoder<'a> {
         let d0 = u64::from(self.consume_time_digit()?);
         let d1 = u64::from(self.consume_time_digit()?);
         let d2 = u64::from(self.consume_time_digit()?);
-        match self.bytes.first().copied() {
-            Some(b'Z' | b'+' | b'~' | b',' | b']') | None => {}
-            _ => return Err(self.err(ErrorReason::MalformedTime)),
+        if !matches!(self.bytes.first(), Some(b'Z' | b'+' | b'~')) && !self.at_value_end() {
+            return Err(self.err(ErrorReason::MalformedTime));
         }
         let ns = 100 * d0 + 10 * d1 + d2;
         Ok(ns)
@@ -739,22 +2023,20 @@ impl<'a> Decoder<'a> {
         if !(-23..=23).contains(&h) {
             return Err(self.err(ErrorReason::TimezoneOffsetHourOutOfRange));
         }
-        match self.bytes.first().copied() {
-            Some(b':') => self.consume_byte(),
-            _ => {
-                if h == 0 {
-                    return Err(self.err(ErrorReason::ZeroTimeZoneOffsetShouldBeZ));
-                }
-                return Ok(TzOffset { h, m: 0 });
+        if self.consume_exact(b':').is_none() {
+            if h == 0 {
+                self.deviation(ErrorReason::ZeroTimeZoneOffsetShouldBeZ)?;
             }
-        };
+            return Ok(TzOffset { h, m: 0 });
+        }
         let d0 = self.consume_tz_offset_digit()?;
         let d1 = self.consume_tz_offset_digit()?;
         let m = 10 * d0 + d1;
         if !(0..=59).contains(&m) {
             Err(self.err(ErrorReason::TimezoneOffsetMinuteOutOfRange))
         } else if h == 0 && m == 0 {
-            Err(self.err(ErrorReason::ZeroTimeZoneOffsetShouldBeZ))
+            self.deviation(ErrorReason::ZeroTimeZoneOffsetShouldBeZ)?;
+            Ok(TzOffset { h, m })
         } else if m == 0 {
             Err(self.err(ErrorReason::ZeroTimeZoneMinutesShouldBeOmitted))
         } else {
@@ -786,9 +2068,10 @@ impl<'a> Decoder<'a> {
             }
             Some(Date::YearMonthDay { .. } | Date::YearWeekDay { .. }) | None => {}
         }
-        let opt_tz_offset = match self.bytes.first() {
-            None | Some(&b',' | &b']') => None,
-            _ => Some(self.consume_tz_offset()?),
+        let opt_tz_offset = if self.at_value_end() {
+            None
+        } else {
+            Some(self.consume_tz_offset()?)
         };
         self.close_item(ErrorReason::MalformedDateTimeTzOffset)?;
         match (opt_date, opt_time, opt_tz_offset) {
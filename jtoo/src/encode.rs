@@ -1,8 +1,13 @@
-use std::iter::repeat;
+use crate::time::{days_in_month, weeks_in_year};
+use core::fmt::Display;
+use std::borrow::Cow;
+use std::io;
+use std::iter::{repeat, repeat_n};
 use std::ops::Rem;
+use std::sync::Arc;
 
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug)]
 pub enum EncodeError {
     NotInByteString,
     NotInList,
@@ -11,6 +16,7 @@ pub enum EncodeError {
     UnclosedList,
     UnclosedString,
     InvalidTimestamp,
+    InvalidDuration,
     InvalidYear,
     InvalidMonth,
     InvalidHour,
@@ -19,10 +25,120 @@ pub enum EncodeError {
     InvalidSecond,
     InvalidWeek,
     InvalidWeekday,
+    /// The day is past the end of the month, like February 30.
+    InvalidDayOfMonth,
+    /// Week 53 in an ISO 8601 week-numbering year with 52 weeks.
+    InvalidWeekOfYear,
     InvalidNanosecond,
     InvalidMicrosecond,
     InvalidMillisecond,
     InvalidTimezoneOffset,
+    /// A time with a date that has no day, like `DateTimeTzOffset::DateTime(Date::Year { .. }, _)`.
+    InvalidDateTime,
+    NotUtf8,
+    /// Writing to the [`io::Write`] from [`Encoder::to_writer`] failed.
+    /// Errors with the same [`io::ErrorKind`] compare equal.
+    Io(Arc<io::Error>),
+    /// Writing to the [`core::fmt::Write`] from [`Encoder::to_fmt_writer`] failed.
+    Fmt,
+    /// [`Encoder::into_string`] or [`Encoder::as_str`] was called on an encoder that writes its
+    /// output to a writer.  Call [`Encoder::finish`] instead.
+    HasWriter,
+    /// An error with a description of the value being encoded, from [`EncodeError::with_context`].
+    /// It compares equal to the error inside it.
+    Context(Cow<'static, str>, Box<EncodeError>),
+}
+impl EncodeError {
+    /// Adds a description of the value being encoded, like a type or field name.
+    /// Call it as the error passes out of each encoder.
+    /// Like [`crate::DecodeError::with_context`], the descriptions follow the reason in `Display`.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::EncodeError;
+    /// let err = EncodeError::InvalidMonth
+    ///     .with_context("Item.date")
+    ///     .with_context("Order");
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "invalid month while encoding Item.date while encoding Order"
+    /// );
+    /// assert_eq!(err, EncodeError::InvalidMonth);
+    /// ```
+    #[must_use]
+    pub fn with_context(self, context: impl Into<Cow<'static, str>>) -> Self {
+        EncodeError::Context(context.into(), Box::new(self))
+    }
+
+    /// Returns the error inside any [`EncodeError::Context`] wrappers.
+    #[must_use]
+    pub fn root(&self) -> &EncodeError {
+        match self {
+            EncodeError::Context(_, source) => source.root(),
+            _ => self,
+        }
+    }
+}
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodeError::NotInByteString => f.write_str("not in a byte string"),
+            EncodeError::NotInList => f.write_str("not in a list"),
+            EncodeError::NotInString => f.write_str("not in a string"),
+            EncodeError::UnclosedByteString => f.write_str("byte string is not closed"),
+            EncodeError::UnclosedList => f.write_str("list is not closed"),
+            EncodeError::UnclosedString => f.write_str("string is not closed"),
+            EncodeError::InvalidTimestamp => f.write_str("timestamp is out of range"),
+            EncodeError::InvalidDuration => f.write_str("duration is out of range"),
+            EncodeError::InvalidYear => f.write_str("invalid year"),
+            EncodeError::InvalidMonth => f.write_str("invalid month"),
+            EncodeError::InvalidHour => f.write_str("invalid hour"),
+            EncodeError::InvalidMinute => f.write_str("invalid minute"),
+            EncodeError::InvalidDay => f.write_str("invalid day"),
+            EncodeError::InvalidSecond => f.write_str("invalid second"),
+            EncodeError::InvalidWeek => f.write_str("invalid week"),
+            EncodeError::InvalidWeekday => f.write_str("invalid weekday"),
+            EncodeError::InvalidDayOfMonth => f.write_str("day is past the end of the month"),
+            EncodeError::InvalidWeekOfYear => {
+                f.write_str("week is past the end of the ISO week-numbering year")
+            }
+            EncodeError::InvalidNanosecond => f.write_str("invalid nanosecond"),
+            EncodeError::InvalidMicrosecond => f.write_str("invalid microsecond"),
+            EncodeError::InvalidMillisecond => f.write_str("invalid millisecond"),
+            EncodeError::InvalidTimezoneOffset => f.write_str("invalid timezone offset"),
+            EncodeError::InvalidDateTime => f.write_str("time needs a date with a day"),
+            EncodeError::NotUtf8 => f.write_str("string is not valid UTF-8"),
+            EncodeError::Io(_) | EncodeError::Fmt => f.write_str("error writing output"),
+            EncodeError::HasWriter => f.write_str("output goes to a writer"),
+            EncodeError::Context(context, source) => {
+                write!(f, "{source} while encoding {context}")
+            }
+        }
+    }
+}
+impl PartialEq for EncodeError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.root(), other.root()) {
+            (EncodeError::Io(a), EncodeError::Io(b)) => a.kind() == b.kind(),
+            (a, b) => core::mem::discriminant(a) == core::mem::discriminant(b),
+        }
+    }
+}
+impl Eq for EncodeError {}
+impl From<io::Error> for EncodeError {
+    fn from(e: io::Error) -> Self {
+        EncodeError::Io(Arc::new(e))
+    }
+}
+impl std::error::Error for EncodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EncodeError::Io(e) => Some(e.as_ref()),
+            // The wrapped error is part of `Display`, so it is not a separate source.
+            EncodeError::Context(_, source) => source.source(),
+            _ => None,
+        }
+    }
 }
 
 pub trait Encode {
@@ -35,133 +151,166 @@ pub trait Encode {
         self.encode_using(&mut encoder)?;
         encoder.into_string()
     }
+
+    /// Encodes the value with one list item per line, like [`Encoder::pretty`].
+    #[allow(clippy::missing_errors_doc)]
+    fn encode_pretty(&self) -> Result<String, EncodeError> {
+        let mut encoder = Encoder::new().pretty();
+        self.encode_using(&mut encoder)?;
+        encoder.into_string()
+    }
+
+    /// Encodes the value to `writer` in chunks, without holding the whole output in memory.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::Encode;
+    /// let mut bytes = Vec::new();
+    /// "a\"b".to_string().encode_to_writer(&mut bytes).unwrap();
+    /// assert_eq!(bytes, br#""a\22b""#);
+    /// ```
+    #[allow(clippy::missing_errors_doc)]
+    fn encode_to_writer(&self, writer: impl io::Write + Send + Sync) -> Result<(), EncodeError>
+    where
+        Self: Sized,
+    {
+        let mut encoder = Encoder::to_writer(writer);
+        self.encode_using(&mut encoder)?;
+        encoder.finish()
+    }
 }
 
 #[derive(Debug)]
-pub struct YearAppender<'x>(&'x mut Encoder);
-impl<'x> YearAppender<'x> {
+pub struct YearAppender<'x, 'w>(&'x mut Encoder<'w>, u16);
+impl<'x, 'w> YearAppender<'x, 'w> {
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_month(self, month: u8) -> Result<MonthAppender<'x>, EncodeError> {
+    pub fn append_month(self, month: u8) -> Result<MonthAppender<'x, 'w>, EncodeError> {
         self.0.append_month(month)?;
-        Ok(MonthAppender(self.0))
+        Ok(MonthAppender(self.0, self.1, month))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_week(self, week: u8) -> Result<WeekAppender<'x>, EncodeError> {
-        self.0.append_week(week)?;
+    pub fn append_week(self, week: u8) -> Result<WeekAppender<'x, 'w>, EncodeError> {
+        self.0.append_week(self.1, week)?;
         Ok(WeekAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder, EncodeError> {
+    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder<'w>, EncodeError> {
         self.0.append_tzoffset(hour, minute)?;
         Ok(self.0)
     }
 }
 
 #[derive(Debug)]
-pub struct MonthAppender<'x>(&'x mut Encoder);
-impl<'x> MonthAppender<'x> {
+pub struct MonthAppender<'x, 'w>(&'x mut Encoder<'w>, u16, u8);
+impl<'x, 'w> MonthAppender<'x, 'w> {
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_day(self, day: u8) -> Result<DayAppender<'x>, EncodeError> {
-        self.0.append_day(day)?;
+    pub fn append_day(self, day: u8) -> Result<DayAppender<'x, 'w>, EncodeError> {
+        self.0.append_day(self.1, self.2, day)?;
         Ok(DayAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder, EncodeError> {
+    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder<'w>, EncodeError> {
         self.0.append_tzoffset(hour, minute)?;
         Ok(self.0)
     }
 }
 
 #[derive(Debug)]
-pub struct WeekAppender<'x>(&'x mut Encoder);
-impl<'x> WeekAppender<'x> {
+pub struct WeekAppender<'x, 'w>(&'x mut Encoder<'w>);
+impl<'x, 'w> WeekAppender<'x, 'w> {
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_weekday(self, weekday: u8) -> Result<DayAppender<'x>, EncodeError> {
+    pub fn append_weekday(self, weekday: u8) -> Result<DayAppender<'x, 'w>, EncodeError> {
         self.0.append_weekday(weekday)?;
         Ok(DayAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder, EncodeError> {
+    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder<'w>, EncodeError> {
         self.0.append_tzoffset(hour, minute)?;
         Ok(self.0)
     }
 }
 
 #[derive(Debug)]
-pub struct DayAppender<'x>(&'x mut Encoder);
-impl<'x> DayAppender<'x> {
+pub struct DayAppender<'x, 'w>(&'x mut Encoder<'w>);
+impl<'x, 'w> DayAppender<'x, 'w> {
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_hour(self, hour: u8) -> Result<HourAppender<'x>, EncodeError> {
+    pub fn append_hour(self, hour: u8) -> Result<HourAppender<'x, 'w>, EncodeError> {
         self.0.append_hour_internal(hour)?;
         Ok(HourAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder, EncodeError> {
+    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder<'w>, EncodeError> {
         self.0.append_tzoffset(hour, minute)?;
         Ok(self.0)
     }
 }
 
 #[derive(Debug)]
-pub struct HourAppender<'x>(&'x mut Encoder);
-impl<'x> HourAppender<'x> {
+pub struct HourAppender<'x, 'w>(&'x mut Encoder<'w>);
+impl<'x, 'w> HourAppender<'x, 'w> {
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_minute(self, minute: u8) -> Result<MinuteAppender<'x>, EncodeError> {
+    pub fn append_minute(self, minute: u8) -> Result<MinuteAppender<'x, 'w>, EncodeError> {
         self.0.append_minute(minute)?;
         Ok(MinuteAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder, EncodeError> {
+    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder<'w>, EncodeError> {
         self.0.append_tzoffset(hour, minute)?;
         Ok(self.0)
     }
 }
 
 #[derive(Debug)]
-pub struct MinuteAppender<'x>(&'x mut Encoder);
-impl<'x> MinuteAppender<'x> {
+pub struct MinuteAppender<'x, 'w>(&'x mut Encoder<'w>);
+impl<'x, 'w> MinuteAppender<'x, 'w> {
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_second(self, second: u8) -> Result<SecondAppender<'x>, EncodeError> {
+    pub fn append_second(self, second: u8) -> Result<SecondAppender<'x, 'w>, EncodeError> {
         self.0.append_second(second)?;
         Ok(SecondAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_millisecond(self, millisecond: u32) -> Result<SecondAppender<'x>, EncodeError> {
+    pub fn append_millisecond(
+        self,
+        millisecond: u32,
+    ) -> Result<SecondAppender<'x, 'w>, EncodeError> {
         self.0.append_millisecond(millisecond)?;
         Ok(SecondAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_microsecond(self, microsecond: u32) -> Result<SecondAppender<'x>, EncodeError> {
+    pub fn append_microsecond(
+        self,
+        microsecond: u32,
+    ) -> Result<SecondAppender<'x, 'w>, EncodeError> {
         self.0.append_microsecond(microsecond)?;
         Ok(SecondAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_nanosecond(self, nanosecond: u64) -> Result<SecondAppender<'x>, EncodeError> {
+    pub fn append_nanosecond(self, nanosecond: u64) -> Result<SecondAppender<'x, 'w>, EncodeError> {
         self.0.append_nanosecond(nanosecond)?;
         Ok(SecondAppender(self.0))
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder, EncodeError> {
+    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder<'w>, EncodeError> {
         self.0.append_tzoffset(hour, minute)?;
         Ok(self.0)
     }
 }
 
 #[derive(Debug)]
-pub struct SecondAppender<'x>(&'x mut Encoder);
-impl<'x> SecondAppender<'x> {
+pub struct SecondAppender<'x, 'w>(&'x mut Encoder<'w>);
+impl<'x, 'w> SecondAppender<'x, 'w> {
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder, EncodeError> {
+    pub fn append_tzoffset(self, hour: i8, minute: u8) -> Result<&'x mut Encoder<'w>, EncodeError> {
         self.0.append_tzoffset(hour, minute)?;
         Ok(self.0)
     }
@@ -175,34 +324,156 @@ enum Elem {
     List,
 }
 
+/// The writers are `Send + Sync` so that [`Encoder`] is too.
+enum Sink<'w> {
+    Io(Box<dyn io::Write + Send + Sync + 'w>),
+    Fmt(Box<dyn core::fmt::Write + Send + Sync + 'w>),
+}
+impl core::fmt::Debug for Sink<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Sink::Io(_) => f.write_str("Io"),
+            Sink::Fmt(_) => f.write_str("Fmt"),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
-pub struct Encoder {
+pub struct Encoder<'w> {
     stack: Vec<Elem>,
     string: String,
+    sink: Option<Sink<'w>>,
+    pretty: bool,
 }
-impl Encoder {
+impl<'w> Encoder<'w> {
     const DIGITS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+    /// Output is written to the sink in chunks of at least this many bytes.
+    const CHUNK_LEN: usize = 8 * 1024;
 
     #[must_use]
     pub fn new() -> Self {
         Self {
             stack: vec![],
             string: String::new(),
+            sink: None,
+            pretty: false,
+        }
+    }
+
+    /// Makes an encoder that writes its output to `writer`.
+    /// You must call [`Encoder::finish`] to write the end of the output and flush `writer`.
+    /// Dropping the encoder without it loses the output that is not written yet.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::Encoder;
+    /// let mut bytes = Vec::new();
+    /// let mut encoder = Encoder::to_writer(&mut bytes);
+    /// encoder.open_list().unwrap();
+    /// encoder.append_integer(1).unwrap();
+    /// encoder.close_list().unwrap();
+    /// encoder.finish().unwrap();
+    /// assert_eq!(bytes, b"[1]");
+    /// ```
+    #[must_use]
+    pub fn to_writer(writer: impl io::Write + Send + Sync + 'w) -> Self {
+        Self {
+            stack: vec![],
+            string: String::new(),
+            sink: Some(Sink::Io(Box::new(writer))),
+            pretty: false,
+        }
+    }
+
+    /// Makes an encoder that writes its output to `writer`, like a [`String`].
+    /// You must call [`Encoder::finish`] to write the end of the output.
+    #[must_use]
+    pub fn to_fmt_writer(writer: impl core::fmt::Write + Send + Sync + 'w) -> Self {
+        Self {
+            stack: vec![],
+            string: String::new(),
+            sink: Some(Sink::Fmt(Box::new(writer))),
+            pretty: false,
+        }
+    }
+
+    /// Puts each list item on its own line, indented by two spaces per level, for people to read.
+    /// Empty lists stay `[]`.
+    ///
+    /// Decode the output with [`crate::DecoderOptions::whitespace_and_comments`].
+    /// It is not valid JTOO.
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::Encoder;
+    /// let mut encoder = Encoder::new().pretty();
+    /// encoder.open_list().unwrap();
+    /// encoder.append_integer(1).unwrap();
+    /// encoder.open_list().unwrap();
+    /// encoder.close_list().unwrap();
+    /// encoder.close_list().unwrap();
+    /// assert_eq!(encoder.as_str(), Ok("[\n  1,\n  []\n]"));
+    /// ```
+    #[must_use]
+    pub fn pretty(mut self) -> Self {
+        self.pretty = true;
+        self
+    }
+
+    /// Starts a new line at the indentation of the innermost open list.
+    fn append_newline(&mut self) {
+        if self.pretty {
+            self.string.push('\n');
+            self.string.extend(repeat_n(' ', 2 * self.stack.len()));
+        }
+    }
+
+    fn check_closed(&self) -> Result<(), EncodeError> {
+        match self.stack.last() {
+            Some(Elem::String) => Err(EncodeError::UnclosedString),
+            Some(Elem::ByteString) => Err(EncodeError::UnclosedByteString),
+            Some(Elem::EmptyList | Elem::List) => Err(EncodeError::UnclosedList),
+            None => Ok(()),
+        }
+    }
+
+    fn write_to_sink(&mut self) -> Result<(), EncodeError> {
+        match &mut self.sink {
+            None => return Ok(()),
+            Some(Sink::Io(writer)) => writer
+                .write_all(self.string.as_bytes())
+                .map_err(EncodeError::from)?,
+            Some(Sink::Fmt(writer)) => writer
+                .write_str(&self.string)
+                .map_err(|_| EncodeError::Fmt)?,
+        }
+        self.string.clear();
+        Ok(())
+    }
+
+    fn write_full_chunk(&mut self) -> Result<(), EncodeError> {
+        if Self::CHUNK_LEN <= self.string.len() {
+            self.write_to_sink()
+        } else {
+            Ok(())
         }
     }
 
     fn prepare_for_new_value(&mut self) -> Result<(), EncodeError> {
+        self.write_full_chunk()?;
         match self.stack.last() {
             Some(Elem::String) => Err(EncodeError::UnclosedString),
             Some(Elem::ByteString) => Err(EncodeError::UnclosedByteString),
             Some(Elem::EmptyList) => {
                 self.stack.pop();
                 self.stack.push(Elem::List);
+                self.append_newline();
                 Ok(())
             }
             None => Ok(()),
             Some(Elem::List) => {
                 self.string.push(',');
+                self.append_newline();
                 Ok(())
             }
         }
@@ -228,19 +499,25 @@ impl Encoder {
         Ok(())
     }
 
-    pub(crate) fn append_day(&mut self, day: u8) -> Result<(), EncodeError> {
+    pub(crate) fn append_day(&mut self, year: u16, month: u8, day: u8) -> Result<(), EncodeError> {
         if !(1..=31).contains(&day) {
             return Err(EncodeError::InvalidDay);
         }
+        if days_in_month(year, month) < day {
+            return Err(EncodeError::InvalidDayOfMonth);
+        }
         self.string.push('-');
         self.append_2_digits(day);
         Ok(())
     }
 
-    pub(crate) fn append_week(&mut self, week: u8) -> Result<(), EncodeError> {
+    pub(crate) fn append_week(&mut self, year: u16, week: u8) -> Result<(), EncodeError> {
         if !(1..=53).contains(&week) {
             return Err(EncodeError::InvalidWeek);
         }
+        if weeks_in_year(year) < week {
+            return Err(EncodeError::InvalidWeekOfYear);
+        }
         self.string.push('-');
         self.string.push('W');
         self.append_2_digits(week);
@@ -291,7 +568,8 @@ impl Encoder {
         if millisecond < 10_000 {
             self.string.push('0');
         }
-        self.append_decimal(i64::from(millisecond), -3)
+        self.append_decimal_digits(i64::from(millisecond), -3);
+        Ok(())
     }
 
     pub(crate) fn append_microsecond(&mut self, microsecond: u32) -> Result<(), EncodeError> {
@@ -302,7 +580,8 @@ impl Encoder {
         if microsecond < 10_000_000 {
             self.string.push('0');
         }
-        self.append_decimal(i64::from(microsecond), -6)
+        self.append_decimal_digits(i64::from(microsecond), -6);
+        Ok(())
     }
 
     pub(crate) fn append_nanosecond(&mut self, nanosecond: u64) -> Result<(), EncodeError> {
@@ -314,20 +593,23 @@ impl Encoder {
         if nanosecond < 10_000_000_000 {
             self.string.push('0');
         }
-        self.append_decimal(value, -9)
+        self.append_decimal_digits(value, -9);
+        Ok(())
     }
 
+    /// `Z`, `+05`, `~05:30`
     pub(crate) fn append_tzoffset(&mut self, hour: i8, minute: u8) -> Result<(), EncodeError> {
-        if !(-12..=12).contains(&hour) || !(0..=59).contains(&minute) {
+        if !(-23..=23).contains(&hour) || !(0..=59).contains(&minute) {
             return Err(EncodeError::InvalidTimezoneOffset);
         }
         if hour == 0 && minute == 0 {
             self.string.push('Z');
             return Ok(());
         }
-        self.string.push(if -1 < hour { '+' } else { '-' });
+        self.string.push(if -1 < hour { '+' } else { '~' });
         self.append_2_digits(hour.unsigned_abs());
         if minute > 0 {
+            self.string.push(':');
             self.append_2_digits(minute);
         }
         Ok(())
@@ -360,7 +642,7 @@ impl Encoder {
                 self.string.push(c1);
                 self.string.push(c2);
             }
-            Ok(())
+            self.write_full_chunk()
         } else {
             Err(EncodeError::NotInByteString)
         }
@@ -378,34 +660,28 @@ impl Encoder {
 
     /// `D2023`
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_year(&mut self, mut year: u16) -> Result<YearAppender, EncodeError> {
+    pub fn append_year(&mut self, year: u16) -> Result<YearAppender<'_, 'w>, EncodeError> {
         self.prepare_for_new_value()?;
         if !(1..=9999).contains(&year) {
             return Err(EncodeError::InvalidYear);
         }
         self.string.push('D');
-        let d3 = year % 10;
-        year /= 10;
-        let d2 = year % 10;
-        year /= 10;
-        let d1 = year % 10;
-        let d0 = year / 10;
-        if d0 != 0 {
-            self.string.push(Self::DIGITS[d0 as usize]);
-        }
-        if d0 != 0 || d1 != 0 {
-            self.string.push(Self::DIGITS[d1 as usize]);
+        let mut rest = year;
+        let d3 = rest % 10;
+        rest /= 10;
+        let d2 = rest % 10;
+        rest /= 10;
+        let d1 = rest % 10;
+        let d0 = rest / 10;
+        for d in [d0, d1, d2, d3] {
+            self.string.push(Self::DIGITS[d as usize]);
         }
-        if d0 != 0 || d1 != 0 || d2 != 0 {
-            self.string.push(Self::DIGITS[d2 as usize]);
-        }
-        self.string.push(Self::DIGITS[d3 as usize]);
-        Ok(YearAppender(self))
+        Ok(YearAppender(self, year))
     }
 
     /// `T23`
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_hour(&mut self, hour: u8) -> Result<HourAppender, EncodeError> {
+    pub fn append_hour(&mut self, hour: u8) -> Result<HourAppender<'_, 'w>, EncodeError> {
         self.prepare_for_new_value()?;
         self.append_hour_internal(hour)?;
         Ok(HourAppender(self))
@@ -415,6 +691,11 @@ impl Encoder {
     #[allow(clippy::missing_errors_doc)]
     pub fn append_decimal(&mut self, value: i64, base10_exponent: i8) -> Result<(), EncodeError> {
         self.prepare_for_new_value()?;
+        self.append_decimal_digits(value, base10_exponent);
+        Ok(())
+    }
+
+    fn append_decimal_digits(&mut self, value: i64, base10_exponent: i8) {
         if value == 0 {
             if -1 < base10_exponent {
                 self.string.push_str("0.0");
@@ -427,7 +708,7 @@ impl Encoder {
                     self.string.push('0');
                 }
             }
-            return Ok(());
+            return;
         }
         if value.is_negative() {
             self.string.push('-');
@@ -450,8 +731,7 @@ impl Encoder {
         if lhs_len == 0 {
             self.string.push('0');
         }
-        for (n, digit) in repeat(0)
-            .take(rhs_len.saturating_sub(digits_len))
+        for (n, digit) in repeat_n(0, rhs_len.saturating_sub(digits_len))
             .chain(digits_array.iter().take(digits_len).rev().copied())
             .chain(repeat(0))
             .take(lhs_len + rhs_len)
@@ -471,15 +751,11 @@ impl Encoder {
         if 0 <= base10_exponent {
             self.string.push_str(".0");
         }
-        Ok(())
     }
 
-    /// `1_234`
-    #[allow(clippy::missing_errors_doc)]
-    pub fn append_integer(&mut self, value: i64) -> Result<(), EncodeError> {
-        self.prepare_for_new_value()?;
-        let digits = value.unsigned_abs().to_string();
-        if value.is_negative() {
+    fn append_digits(&mut self, is_negative: bool, magnitude: u128) {
+        let digits = magnitude.to_string();
+        if is_negative {
             self.string.push('-');
         }
         let mut has_prev = false;
@@ -491,6 +767,33 @@ impl Encoder {
             self.string.push(c);
             has_prev = true;
         }
+    }
+
+    /// `1_234`
+    #[allow(clippy::missing_errors_doc)]
+    pub fn append_integer(&mut self, value: i64) -> Result<(), EncodeError> {
+        self.append_i128(i128::from(value))
+    }
+
+    /// `18_446_744_073_709_551_615`
+    #[allow(clippy::missing_errors_doc)]
+    pub fn append_u64(&mut self, value: u64) -> Result<(), EncodeError> {
+        self.append_u128(u128::from(value))
+    }
+
+    /// `-170_141_183_460_469_231_731_687_303_715_884_105_728`
+    #[allow(clippy::missing_errors_doc)]
+    pub fn append_i128(&mut self, value: i128) -> Result<(), EncodeError> {
+        self.prepare_for_new_value()?;
+        self.append_digits(value.is_negative(), value.unsigned_abs());
+        Ok(())
+    }
+
+    /// `340_282_366_920_938_463_463_374_607_431_768_211_455`
+    #[allow(clippy::missing_errors_doc)]
+    pub fn append_u128(&mut self, value: u128) -> Result<(), EncodeError> {
+        self.prepare_for_new_value()?;
+        self.append_digits(false, value);
         Ok(())
     }
 
@@ -505,9 +808,15 @@ impl Encoder {
     #[allow(clippy::missing_errors_doc)]
     pub fn close_list(&mut self) -> Result<(), EncodeError> {
         match self.stack.last() {
-            Some(&Elem::EmptyList | &Elem::List) => {
+            Some(&Elem::EmptyList) => {
+                self.stack.pop();
                 self.string.push(']');
+                Ok(())
+            }
+            Some(&Elem::List) => {
                 self.stack.pop();
+                self.append_newline();
+                self.string.push(']');
                 Ok(())
             }
             _ => Err(EncodeError::NotInList),
@@ -544,7 +853,7 @@ impl Encoder {
                 _ => self.string.push(c),
             }
         }
-        Ok(())
+        self.write_full_chunk()
     }
 
     /// `"`
@@ -559,59 +868,107 @@ impl Encoder {
         }
     }
 
-    /// `S1_234`
+    /// `S1_234`, `S-1_234`
+    ///
+    /// Timestamps are seconds since the Unix epoch, so negative values are before 1970.
+    /// Fractional timestamps are decimals whose sign applies to the whole value,
+    /// so `S-1.500` is 1.5 seconds before the epoch.
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_timestamp_seconds(&mut self, s: u64) -> Result<(), EncodeError> {
+    pub fn append_timestamp_seconds(&mut self, s: i64) -> Result<(), EncodeError> {
         self.prepare_for_new_value()?;
         self.string.push('S');
-        let value = i64::try_from(s).map_err(|_| EncodeError::InvalidTimestamp)?;
-        self.append_integer(value)
+        self.append_digits(s.is_negative(), u128::from(s.unsigned_abs()));
+        Ok(())
     }
 
-    /// `S1_234.500`
+    /// `S1_234.500`, `S-0.500`
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_timestamp_milliseconds(&mut self, ms: u64) -> Result<(), EncodeError> {
+    pub fn append_timestamp_milliseconds(&mut self, ms: i64) -> Result<(), EncodeError> {
         self.prepare_for_new_value()?;
         self.string.push('S');
-        let value = i64::try_from(ms).map_err(|_| EncodeError::InvalidTimestamp)?;
-        self.append_decimal(value, -3)
+        self.append_decimal_digits(ms, -3);
+        Ok(())
     }
 
-    /// `S1_234.567_800`
+    /// `S1_234.567_800`, `S-0.000_500`
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_timestamp_microseconds(&mut self, us: u64) -> Result<(), EncodeError> {
+    pub fn append_timestamp_microseconds(&mut self, us: i64) -> Result<(), EncodeError> {
         self.prepare_for_new_value()?;
         self.string.push('S');
-        let value = i64::try_from(us).map_err(|_| EncodeError::InvalidTimestamp)?;
-        self.append_decimal(value, -6)
+        self.append_decimal_digits(us, -6);
+        Ok(())
     }
 
-    /// `S1_234.567_890_100`
+    /// `S1_234.567_890_100`, `S-0.000_000_500`
     #[allow(clippy::missing_errors_doc)]
-    pub fn append_timestamp_nanosecond(&mut self, ns: u64) -> Result<(), EncodeError> {
+    pub fn append_timestamp_nanosecond(&mut self, ns: i64) -> Result<(), EncodeError> {
         self.prepare_for_new_value()?;
         self.string.push('S');
-        let value = i64::try_from(ns).map_err(|_| EncodeError::InvalidTimestamp)?;
-        self.append_decimal(value, -9)
+        self.append_decimal_digits(ns, -9);
+        Ok(())
     }
 
-    #[allow(clippy::missing_errors_doc)]
+    /// Returns the output.
+    ///
+    /// # Errors
+    /// Returns `Err` when a string or list is not closed,
+    /// or the encoder writes to a writer, from [`Encoder::to_writer`] or [`Encoder::to_fmt_writer`].
     pub fn into_string(self) -> Result<String, EncodeError> {
-        match self.stack.last() {
-            Some(Elem::String) => Err(EncodeError::UnclosedString),
-            Some(Elem::ByteString) => Err(EncodeError::UnclosedByteString),
-            Some(Elem::EmptyList | Elem::List) => Err(EncodeError::UnclosedList),
-            None => Ok(self.string),
+        self.check_no_writer()?;
+        self.check_closed()?;
+        Ok(self.string)
+    }
+
+    fn check_no_writer(&self) -> Result<(), EncodeError> {
+        if self.sink.is_some() {
+            return Err(EncodeError::HasWriter);
         }
+        Ok(())
     }
 
-    #[allow(clippy::missing_errors_doc)]
-    pub fn as_str(&self) -> Result<&str, EncodeError> {
-        match self.stack.last() {
-            Some(Elem::String) => Err(EncodeError::UnclosedString),
-            Some(Elem::ByteString) => Err(EncodeError::UnclosedByteString),
-            Some(Elem::EmptyList | Elem::List) => Err(EncodeError::UnclosedList),
-            None => Ok(self.string.as_str()),
+    /// Writes the rest of the output to the writer from [`Encoder::to_writer`]
+    /// or [`Encoder::to_fmt_writer`] and flushes it.
+    /// An encoder with a writer must end with this call.
+    ///
+    /// # Errors
+    /// Returns `Err` when a string or list is not closed, or writing fails.
+    pub fn finish(mut self) -> Result<(), EncodeError> {
+        self.check_closed()?;
+        self.write_to_sink()?;
+        if let Some(Sink::Io(writer)) = &mut self.sink {
+            writer.flush().map_err(EncodeError::from)?;
         }
+        Ok(())
+    }
+
+    /// Ends a top-level value in a stream of newline-separated records,
+    /// read by [`crate::Decoder::records`] and [`crate::RecordReader`].
+    ///
+    /// # Example
+    /// ```
+    /// use jtoo::Encoder;
+    /// let mut encoder = Encoder::new();
+    /// encoder.append_integer(1).unwrap();
+    /// encoder.append_record_separator().unwrap();
+    /// encoder.append_integer(2).unwrap();
+    /// encoder.append_record_separator().unwrap();
+    /// assert_eq!(encoder.as_str(), Ok("1\n2\n"));
+    /// ```
+    #[allow(clippy::missing_errors_doc)]
+    pub fn append_record_separator(&mut self) -> Result<(), EncodeError> {
+        self.check_closed()?;
+        self.string.push('\n');
+        self.write_full_chunk()
+    }
+
+    /// Returns the output so far.
+    ///
+    /// # Errors
+    /// Returns `Err` when a string or list is not closed,
+    /// or the encoder writes to a writer, from [`Encoder::to_writer`] or [`Encoder::to_fmt_writer`].
+    pub fn as_str(&self) -> Result<&str, EncodeError> {
+        self.check_no_writer()?;
+        self.check_closed()?;
+        Ok(self.string.as_str())
     }
 }
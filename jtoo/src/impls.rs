@@ -0,0 +1,280 @@
+use crate::{
+    Date, DateTimeTzOffset, Decode, DecodeError, Decoder, Encode, EncodeError, Encoder,
+    ErrorReason, HourAppender, Time, Timestamp, TimestampPrecision, TzOffset, ValueKind,
+};
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::path::{Path, PathBuf};
+
+fn encode_str(s: &str, encoder: &mut Encoder) -> Result<(), EncodeError> {
+    encoder.open_string()?;
+    encoder.append_string(s)?;
+    encoder.close_string()
+}
+
+impl Encode for str {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_str(self, encoder)
+    }
+}
+impl Encode for String {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_str(self, encoder)
+    }
+}
+impl Encode for Cow<'_, str> {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_str(self, encoder)
+    }
+}
+impl<'a> Decode<'a> for String {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_string()
+    }
+}
+impl<'a> Decode<'a> for Cow<'a, str> {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_str()
+    }
+}
+/// Fails with `EscapedStringNotBorrowable` when the string contains escape sequences.
+/// Use `Cow<'a, str>` to accept those too.
+impl<'a> Decode<'a> for &'a str {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_map(Decoder::consume_str, |s| match s {
+            Cow::Borrowed(s) => Ok(s),
+            Cow::Owned(_) => Err(ErrorReason::EscapedStringNotBorrowable),
+        })
+    }
+}
+
+macro_rules! impl_integer {
+    ($t:ty, $append:ident, $wide:ty, $consume:ident) => {
+        impl Encode for $t {
+            fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+                encoder.$append(<$wide>::from(*self))
+            }
+        }
+        impl<'a> Decode<'a> for $t {
+            fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+                decoder.consume_map(Decoder::$consume, |value| {
+                    Self::try_from(value).map_err(|_| ErrorReason::IntegerTooLarge)
+                })
+            }
+        }
+    };
+}
+
+impl_integer!(i8, append_integer, i64, consume_integer);
+impl_integer!(i16, append_integer, i64, consume_integer);
+impl_integer!(i32, append_integer, i64, consume_integer);
+impl_integer!(i64, append_integer, i64, consume_integer);
+impl_integer!(i128, append_i128, i128, consume_i128);
+impl_integer!(u8, append_u64, u64, consume_u64);
+impl_integer!(u16, append_u64, u64, consume_u64);
+impl_integer!(u32, append_u64, u64, consume_u64);
+impl_integer!(u64, append_u64, u64, consume_u64);
+impl_integer!(u128, append_u128, u128, consume_u128);
+
+// There is no `From<isize> for i128`, but the conversion is lossless.
+impl Encode for isize {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encoder.append_i128(*self as i128)
+    }
+}
+impl<'a> Decode<'a> for isize {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_map(Decoder::consume_i128, |value| {
+            Self::try_from(value).map_err(|_| ErrorReason::IntegerTooLarge)
+        })
+    }
+}
+
+impl Encode for usize {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encoder.append_u128(*self as u128)
+    }
+}
+impl<'a> Decode<'a> for usize {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_map(Decoder::consume_u128, |value| {
+            Self::try_from(value).map_err(|_| ErrorReason::IntegerTooLarge)
+        })
+    }
+}
+
+impl Encode for Timestamp {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        match self.precision {
+            TimestampPrecision::Seconds => encoder.append_timestamp_seconds(self.value),
+            TimestampPrecision::Milliseconds => encoder.append_timestamp_milliseconds(self.value),
+            TimestampPrecision::Microseconds => encoder.append_timestamp_microseconds(self.value),
+            TimestampPrecision::Nanoseconds => encoder.append_timestamp_nanosecond(self.value),
+        }
+    }
+}
+impl<'a> Decode<'a> for Timestamp {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_timestamp()
+    }
+}
+
+/// Appends the time zone offset, if any, to the value in `$appender`.
+macro_rules! append_tz_offset {
+    ($appender:expr, $tz:expr) => {{
+        let appender = $appender;
+        if let Some(tz) = $tz {
+            appender.append_tzoffset(tz.h, tz.m)?;
+        }
+        Ok(())
+    }};
+}
+
+fn encode_time(hour: HourAppender, time: Time, tz: Option<&TzOffset>) -> Result<(), EncodeError> {
+    match time {
+        Time::Hour { .. } => append_tz_offset!(hour, tz),
+        Time::HourMinute { m, .. } => append_tz_offset!(hour.append_minute(m)?, tz),
+        Time::HourMinuteSecond { m, s, .. } => {
+            append_tz_offset!(hour.append_minute(m)?.append_second(s)?, tz)
+        }
+        Time::HourMinuteMillisecond { m, ms, .. } => append_tz_offset!(
+            hour.append_minute(m)?.append_millisecond(u32::from(ms))?,
+            tz
+        ),
+        Time::HourMinuteMicrosecond { m, us, .. } => {
+            append_tz_offset!(hour.append_minute(m)?.append_microsecond(us)?, tz)
+        }
+        Time::HourMinuteNanosecond { m, ns, .. } => {
+            append_tz_offset!(hour.append_minute(m)?.append_nanosecond(ns)?, tz)
+        }
+    }
+}
+
+fn time_hour(time: Time) -> u8 {
+    match time {
+        Time::Hour { h }
+        | Time::HourMinute { h, .. }
+        | Time::HourMinuteSecond { h, .. }
+        | Time::HourMinuteMillisecond { h, .. }
+        | Time::HourMinuteMicrosecond { h, .. }
+        | Time::HourMinuteNanosecond { h, .. } => h,
+    }
+}
+
+impl Encode for DateTimeTzOffset {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        let time = self.time().copied();
+        let tz = self.tz_offset();
+        let Some(date) = self.date() else {
+            let time = time.ok_or(EncodeError::InvalidDateTime)?;
+            return encode_time(encoder.append_hour(time_hour(time))?, time, tz);
+        };
+        let year = encoder.append_year(date.year().ok_or(EncodeError::InvalidYear)?)?;
+        let day = match (*date, time) {
+            (Date::Year { .. }, None) => return append_tz_offset!(year, tz),
+            (Date::YearMonth { mo, .. }, None) => {
+                return append_tz_offset!(year.append_month(mo)?, tz)
+            }
+            (Date::YearWeek { w, .. }, None) => return append_tz_offset!(year.append_week(w)?, tz),
+            (Date::Year { .. } | Date::YearMonth { .. } | Date::YearWeek { .. }, Some(_)) => {
+                return Err(EncodeError::InvalidDateTime)
+            }
+            (Date::YearMonthDay { mo, d, .. }, _) => year.append_month(mo)?.append_day(d)?,
+            (Date::YearWeekDay { w, d, .. }, _) => year.append_week(w)?.append_weekday(d)?,
+        };
+        if let Some(time) = time {
+            encode_time(day.append_hour(time_hour(time))?, time, tz)
+        } else {
+            append_tz_offset!(day, tz)
+        }
+    }
+}
+impl<'a> Decode<'a> for DateTimeTzOffset {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_date_time_tz_offset()
+    }
+}
+
+macro_rules! impl_address {
+    ($t:ty, $reason:expr) => {
+        impl Encode for $t {
+            fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+                encode_str(&self.to_string(), encoder)
+            }
+        }
+        impl<'a> Decode<'a> for $t {
+            fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+                decoder.consume_map(Decoder::consume_str, |s| s.parse().map_err(|_| $reason))
+            }
+        }
+    };
+}
+
+impl_address!(IpAddr, ErrorReason::MalformedIpAddress);
+impl_address!(Ipv4Addr, ErrorReason::MalformedIpAddress);
+impl_address!(Ipv6Addr, ErrorReason::MalformedIpAddress);
+impl_address!(SocketAddr, ErrorReason::MalformedSocketAddress);
+impl_address!(SocketAddrV4, ErrorReason::MalformedSocketAddress);
+impl_address!(SocketAddrV6, ErrorReason::MalformedSocketAddress);
+
+/// OS strings are strings when they are valid UTF-8, and byte strings otherwise.
+fn encode_os_str(s: &OsStr, encoder: &mut Encoder) -> Result<(), EncodeError> {
+    if let Some(s) = s.to_str() {
+        return encode_str(s, encoder);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        encoder.open_byte_string()?;
+        encoder.append_byte_string(s.as_bytes())?;
+        encoder.close_byte_string()
+    }
+    #[cfg(not(unix))]
+    Err(EncodeError::NotUtf8)
+}
+
+fn decode_os_string(decoder: &mut Decoder<'_>) -> Result<OsString, DecodeError> {
+    if decoder.peek_kind() != Some(ValueKind::ByteString) {
+        return decoder.consume_string().map(OsString::from);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        decoder.consume_byte_string().map(OsString::from_vec)
+    }
+    #[cfg(not(unix))]
+    decoder.consume_map(Decoder::consume_byte_string, |_| Err(ErrorReason::NotUtf8))
+}
+
+impl Encode for OsStr {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_os_str(self, encoder)
+    }
+}
+impl Encode for OsString {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_os_str(self, encoder)
+    }
+}
+impl<'a> Decode<'a> for OsString {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decode_os_string(decoder)
+    }
+}
+
+impl Encode for Path {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_os_str(self.as_os_str(), encoder)
+    }
+}
+impl Encode for PathBuf {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_os_str(self.as_os_str(), encoder)
+    }
+}
+impl<'a> Decode<'a> for PathBuf {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decode_os_string(decoder).map(PathBuf::from)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Decode, DecodeError, Decoder, DecoderOptions};
+
+/// The byte offsets of the items in a list, for decoding single items
+/// without decoding the items before them.
+///
+/// # Example
+/// ```
+/// use jtoo::JtooIndex;
+/// let bytes = br#"["a","b","c"]"#;
+/// let index = JtooIndex::build(bytes).unwrap();
+/// assert_eq!(index.len(), 3);
+/// assert_eq!(index.decode::<String>(bytes, 2), Some(Ok("c".to_string())));
+/// assert_eq!(index.decode::<String>(bytes, 3), None);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct JtooIndex {
+    offsets: Vec<usize>,
+    /// The options of the decoder that built the index, for decoding items.
+    options: DecoderOptions,
+}
+impl JtooIndex {
+    /// Indexes the items of the top-level list in `bytes`.
+    ///
+    /// # Errors
+    /// Returns `Err` when `bytes` is not a well-formed list.
+    pub fn build(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut decoder = Decoder::new(bytes);
+        let index = Self::build_using(&mut decoder)?;
+        decoder.close()?;
+        Ok(index)
+    }
+
+    /// Indexes the items of the next list in `decoder` and consumes the list.
+    /// Offsets are from the start of the decoder's input.
+    /// Items are decoded later with the decoder's options.
+    ///
+    /// # Errors
+    /// Returns `Err` when the next value is not a well-formed list.
+    pub fn build_using(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let mut offsets = Vec::new();
+        decoder.consume_list_open()?;
+        while decoder.has_another_list_item() {
+            offsets.push(decoder.offset());
+            decoder.skip_value()?;
+        }
+        decoder.consume_list_close()?;
+        Ok(Self {
+            offsets,
+            options: decoder.options(),
+        })
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    #[must_use]
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// Returns a decoder positioned at item `index`.
+    /// Decode one value with it, and do not call [`Decoder::close`].
+    /// `bytes` must be the input that was indexed.
+    ///
+    /// # Panics
+    /// Panics when `bytes` is shorter than the indexed input.
+    #[must_use]
+    pub fn decoder<'a>(&self, bytes: &'a [u8], index: usize) -> Option<Decoder<'a>> {
+        let offset = *self.offsets.get(index)?;
+        Some(Decoder::at_list_item(bytes, offset, index, self.options))
+    }
+
+    /// Decodes item `index`, or returns `None` when there is no such item.
+    /// `bytes` must be the input that was indexed.
+    ///
+    /// # Panics
+    /// Panics when `bytes` is shorter than the indexed input.
+    #[must_use]
+    pub fn decode<'a, T: Decode<'a>>(
+        &self,
+        bytes: &'a [u8],
+        index: usize,
+    ) -> Option<Result<T, DecodeError>> {
+        let mut decoder = self.decoder(bytes, index)?;
+        Some(T::decode_using(&mut decoder))
+    }
+}
@@ -46,11 +46,20 @@
 //! - v0.1.0 - Initial version.
 #![forbid(unsafe_code)]
 
+mod bytes;
 mod decode;
 mod encode;
+mod impls;
+mod index;
+mod record_reader;
+mod time;
 
+pub use bytes::*;
 pub use decode::*;
 pub use encode::*;
+pub use index::*;
+pub use record_reader::*;
+pub use time::*;
 
 /// Converts a byte slice into a string using
 /// [`core::ascii::escape_default`](https://doc.rust-lang.org/core/ascii/fn.escape_default.html)
@@ -0,0 +1,218 @@
+use crate::decode::ValueFraming;
+use crate::{DecodeError, DecodeOwned, Decoder, DecoderOptions};
+use std::io::Read;
+
+/// The result of [`RecordReader::next_record`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecordValue<T> {
+    Value(T),
+    /// The buffer ends before the newline of the next record.
+    /// Call [`RecordReader::feed`] or [`RecordReader::finish`] and try again.
+    NeedMoreData,
+    /// [`RecordReader::finish`] was called and every record was returned.
+    End,
+}
+
+/// Reads newline-separated records that arrive in chunks, like reads from a socket.
+///
+/// The input has the record format of [`Decoder::records`]: each top-level value is followed
+/// by `\n`, like the output of [`crate::Encoder::append_record_separator`].
+/// The newline is not part of JTOO; it only marks where each record ends.
+/// Each record is buffered until its newline arrives or the input is finished,
+/// and then decoded whole.
+/// So a complete value without a newline, like `[1,2]`, is returned only after
+/// [`RecordReader::finish`].
+///
+/// # Example
+/// ```
+/// use jtoo::{RecordReader, RecordValue};
+/// let mut reader = RecordReader::new();
+/// reader.feed(b"\"ab");
+/// assert_eq!(reader.next_record::<String>(), Ok(RecordValue::NeedMoreData));
+/// reader.feed(b"c\"\n\"d\"");
+/// assert_eq!(reader.next_record(), Ok(RecordValue::Value("abc".to_string())));
+/// assert_eq!(reader.next_record::<String>(), Ok(RecordValue::NeedMoreData));
+/// reader.finish();
+/// assert_eq!(reader.next_record(), Ok(RecordValue::Value("d".to_string())));
+/// assert_eq!(reader.next_record::<String>(), Ok(RecordValue::End));
+/// ```
+#[derive(Debug)]
+pub struct RecordReader {
+    buffer: Vec<u8>,
+    /// The start of the next value in `buffer`.
+    start: usize,
+    /// The bytes from `start` to `scan_pos` contain no value separator.
+    scan_pos: usize,
+    /// The framing state at `scan_pos`.
+    framing: ValueFraming,
+    finished: bool,
+    options: DecoderOptions,
+    /// The input offset of `start`.
+    offset: usize,
+    /// The input line of `start`, starting at 1.
+    line: usize,
+    /// The unfinished record at `start` went over `max_input_len`.
+    /// Its bytes are dropped as they arrive, up to its separator.
+    skipping: bool,
+    /// The `InputTooLarge` error for the dropped record, until `next_record` returns it.
+    too_large: Option<DecodeError>,
+}
+impl RecordReader {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_options(DecoderOptions::default())
+    }
+
+    /// The options apply to each record.
+    /// `max_input_len` also limits how much of an unfinished record is buffered.
+    /// When a record goes over it, the reader drops the record's bytes up to its newline,
+    /// and [`RecordReader::next_record`] returns `InputTooLarge` once.
+    #[must_use]
+    pub fn with_options(options: DecoderOptions) -> Self {
+        Self {
+            buffer: Vec::new(),
+            start: 0,
+            scan_pos: 0,
+            framing: ValueFraming::new(&options),
+            finished: false,
+            options,
+            offset: 0,
+            line: 1,
+            skipping: false,
+            too_large: None,
+        }
+    }
+
+    /// Appends `bytes` to the buffer.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.compact();
+        self.buffer.extend_from_slice(bytes);
+        self.limit_buffer();
+    }
+
+    /// Marks the end of the input.
+    /// The last record does not need a trailing newline.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Reads one chunk from `reader` into the buffer.
+    /// Calls [`RecordReader::finish`] when `reader` is at end of file.
+    ///
+    /// # Errors
+    /// Returns `Err` when `reader` fails.
+    pub fn read_from(&mut self, reader: &mut impl Read) -> std::io::Result<usize> {
+        const CHUNK_LEN: usize = 8 * 1024;
+        self.compact();
+        let len = self.buffer.len();
+        self.buffer.resize(len + CHUNK_LEN, 0);
+        let result = reader.read(&mut self.buffer[len..]);
+        self.buffer
+            .truncate(len + result.as_ref().map_or(0, |n| *n));
+        if matches!(result, Ok(0)) {
+            self.finish();
+        }
+        self.limit_buffer();
+        result
+    }
+
+    /// Drops the unfinished value when it is over `max_input_len`.
+    /// Waits while an earlier dropped value's error is not returned yet.
+    fn limit_buffer(&mut self) {
+        if !self.skipping {
+            if self.too_large.is_some()
+                || self.buffer.len() - self.start <= self.options.max_input_len
+                || self.find_value_end().is_some()
+            {
+                return;
+            }
+            self.too_large = Decoder::with_options(&self.buffer[self.start..], self.options)
+                .err()
+                .map(|e| self.input_err(e));
+            self.skipping = true;
+        }
+        match self.find_value_end() {
+            Some(end) => {
+                self.advance(end + 1);
+                self.skipping = false;
+                self.framing = ValueFraming::new(&self.options);
+            }
+            None => self.advance(self.buffer.len()),
+        }
+    }
+
+    /// Moves `start` to `next`, counting the input position of the bytes before it.
+    #[allow(clippy::naive_bytecount)]
+    fn advance(&mut self, next: usize) {
+        let bytes = &self.buffer[self.start..next];
+        self.offset += bytes.len();
+        self.line += bytes.iter().filter(|b| **b == b'\n').count();
+        self.start = next;
+        self.scan_pos = next;
+    }
+
+    fn compact(&mut self) {
+        if self.start != 0 {
+            self.buffer.drain(..self.start);
+            self.scan_pos -= self.start;
+            self.start = 0;
+        }
+    }
+
+    /// Returns the position of the newline that ends the next value.
+    fn find_value_end(&mut self) -> Option<usize> {
+        let end = self
+            .framing
+            .find_end(&self.buffer[self.scan_pos..])
+            .map(|n| self.scan_pos + n);
+        self.scan_pos = end.unwrap_or(self.buffer.len());
+        end
+    }
+
+    /// Decodes the next record.
+    /// After an error, the reader skips to the next record.
+    ///
+    /// # Errors
+    /// Returns `Err` when the value is malformed, does not decode as `T`,
+    /// or exceeds a limit in the options.
+    /// Error offsets and lines are positions in the whole input.
+    pub fn next_record<T: DecodeOwned>(&mut self) -> Result<RecordValue<T>, DecodeError> {
+        if let Some(e) = self.too_large.take() {
+            return Err(e);
+        }
+        let (end, next) = match self.find_value_end() {
+            Some(end) => (end, end + 1),
+            None if self.finished
+                && (self.start == self.buffer.len() || self.framing.is_blank()) =>
+            {
+                self.advance(self.buffer.len());
+                return Ok(RecordValue::End);
+            }
+            None if self.finished => (self.buffer.len(), self.buffer.len()),
+            None => return Ok(RecordValue::NeedMoreData),
+        };
+        let result = Self::decode(&self.buffer[self.start..end], self.options);
+        let result = result.map_err(|e| self.input_err(e));
+        self.advance(next);
+        self.framing = ValueFraming::new(&self.options);
+        result.map(RecordValue::Value)
+    }
+
+    fn decode<T: DecodeOwned>(bytes: &[u8], options: DecoderOptions) -> Result<T, DecodeError> {
+        let mut decoder = Decoder::with_options(bytes, options)?;
+        let value = T::decode_using(&mut decoder)?;
+        decoder.close()?;
+        Ok(value)
+    }
+
+    fn input_err(&self, mut e: DecodeError) -> DecodeError {
+        e.offset += self.offset;
+        e.line += self.line - 1;
+        e
+    }
+}
+impl Default for RecordReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
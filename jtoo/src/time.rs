@@ -0,0 +1,205 @@
+use crate::{
+    Decode, DecodeError, Decoder, Encode, EncodeError, Encoder, ErrorReason, Timestamp,
+    TimestampPrecision,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+
+/// Years in the proleptic Gregorian calendar.
+pub(crate) fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+pub(crate) fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The number of weeks in an ISO 8601 week-numbering year, 52 or 53.
+/// A year has 53 weeks when it starts on a Thursday,
+/// or when it is a leap year that starts on a Wednesday.
+pub(crate) fn weeks_in_year(year: u16) -> u8 {
+    // The weekday of December 31 of `y`, where 0 is Sunday.
+    let dec_31_weekday = |y: u32| (y + y / 4 - y / 100 + y / 400) % 7;
+    let year = u32::from(year);
+    if dec_31_weekday(year) == 4 || dec_31_weekday(year - 1) == 3 {
+        53
+    } else {
+        52
+    }
+}
+
+impl Timestamp {
+    /// Converts `time` to a count of `precision` units since the Unix epoch,
+    /// rounding down to the start of the unit that contains `time`.
+    /// Returns `None` when the count does not fit in `i64`.
+    #[must_use]
+    pub fn from_system_time(time: SystemTime, precision: TimestampPrecision) -> Option<Self> {
+        let nanos = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => i128::try_from(duration.as_nanos()).ok()?,
+            Err(e) => -i128::try_from(e.duration().as_nanos()).ok()?,
+        };
+        let nanos_per_unit = i128::from(NANOS_PER_SECOND / precision.units_per_second());
+        let value = i64::try_from(nanos.div_euclid(nanos_per_unit)).ok()?;
+        Some(Self { value, precision })
+    }
+
+    /// Returns `None` when the platform's `SystemTime` cannot represent the timestamp.
+    #[must_use]
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let nanos_per_unit = u128::from(NANOS_PER_SECOND / self.precision.units_per_second());
+        let nanos = u128::from(self.value.unsigned_abs()) * nanos_per_unit;
+        let duration = Duration::new(
+            u64::try_from(nanos / u128::from(NANOS_PER_SECOND)).ok()?,
+            u32::try_from(nanos % u128::from(NANOS_PER_SECOND)).ok()?,
+        );
+        if self.value.is_negative() {
+            UNIX_EPOCH.checked_sub(duration)
+        } else {
+            UNIX_EPOCH.checked_add(duration)
+        }
+    }
+}
+
+fn encode_system_time(
+    time: SystemTime,
+    precision: TimestampPrecision,
+    encoder: &mut Encoder,
+) -> Result<(), EncodeError> {
+    Timestamp::from_system_time(time, precision)
+        .ok_or(EncodeError::InvalidTimestamp)?
+        .encode_using(encoder)
+}
+
+fn decode_system_time(decoder: &mut Decoder) -> Result<SystemTime, DecodeError> {
+    decoder.consume_map(Decoder::consume_timestamp, |timestamp| {
+        timestamp
+            .to_system_time()
+            .ok_or(ErrorReason::TimestampTooLarge)
+    })
+}
+
+/// Durations are decimal numbers of seconds: `1.500_000_000`.
+fn encode_duration(
+    duration: Duration,
+    precision: TimestampPrecision,
+    encoder: &mut Encoder,
+) -> Result<(), EncodeError> {
+    let units_per_second = precision.units_per_second();
+    let nanos_per_unit = u128::from(NANOS_PER_SECOND / units_per_second);
+    let value = i64::try_from(duration.as_nanos() / nanos_per_unit)
+        .map_err(|_| EncodeError::InvalidDuration)?;
+    let base10_exponent = match precision {
+        TimestampPrecision::Seconds => 0,
+        TimestampPrecision::Milliseconds => -3,
+        TimestampPrecision::Microseconds => -6,
+        TimestampPrecision::Nanoseconds => -9,
+    };
+    encoder.append_decimal(value, base10_exponent)
+}
+
+fn decimal_to_duration(mantissa: i64, base10_exponent: i8) -> Result<Duration, ErrorReason> {
+    let mantissa = u128::try_from(mantissa).map_err(|_| ErrorReason::NegativeNotAllowed)?;
+    let nanos = if -9 <= base10_exponent {
+        mantissa * 10u128.pow(u32::from(base10_exponent.abs_diff(-9)))
+    } else {
+        let divisor = 10u128
+            .checked_pow(u32::from(base10_exponent.abs_diff(-9)))
+            .ok_or(ErrorReason::DurationTooPrecise)?;
+        if mantissa % divisor != 0 {
+            return Err(ErrorReason::DurationTooPrecise);
+        }
+        mantissa / divisor
+    };
+    let nanos_per_second = u128::from(NANOS_PER_SECOND);
+    Ok(Duration::new(
+        u64::try_from(nanos / nanos_per_second).map_err(|_| ErrorReason::DecimalTooLarge)?,
+        u32::try_from(nanos % nanos_per_second).map_err(|_| ErrorReason::DecimalTooLarge)?,
+    ))
+}
+
+fn decode_duration(decoder: &mut Decoder) -> Result<Duration, DecodeError> {
+    decoder.consume_map(Decoder::consume_decimal, |(mantissa, base10_exponent)| {
+        decimal_to_duration(mantissa, base10_exponent)
+    })
+}
+
+impl Encode for SystemTime {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_system_time(*self, TimestampPrecision::Nanoseconds, encoder)
+    }
+}
+impl<'a> Decode<'a> for SystemTime {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decode_system_time(decoder)
+    }
+}
+
+impl Encode for Duration {
+    fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+        encode_duration(*self, TimestampPrecision::Nanoseconds, encoder)
+    }
+}
+impl<'a> Decode<'a> for Duration {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decode_duration(decoder)
+    }
+}
+
+macro_rules! precision_wrapper {
+    ($(#[$attr:meta])* $name:ident, $precision:expr) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        pub struct $name<T>(pub T);
+        impl Encode for $name<SystemTime> {
+            fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+                encode_system_time(self.0, $precision, encoder)
+            }
+        }
+        impl<'a> Decode<'a> for $name<SystemTime> {
+            fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+                decode_system_time(decoder).map(Self)
+            }
+        }
+        impl Encode for $name<Duration> {
+            fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+                encode_duration(self.0, $precision, encoder)
+            }
+        }
+        impl<'a> Decode<'a> for $name<Duration> {
+            fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+                decode_duration(decoder).map(Self)
+            }
+        }
+    };
+}
+
+precision_wrapper!(
+    /// Encodes a `SystemTime` or `Duration` with whole seconds: `S1_700_000_000`, `90.0`.
+    /// Decoding accepts any precision.
+    InSeconds,
+    TimestampPrecision::Seconds
+);
+precision_wrapper!(
+    /// Encodes a `SystemTime` or `Duration` with milliseconds: `S1_700_000_000.123`, `1.500`.
+    /// Decoding accepts any precision.
+    InMilliseconds,
+    TimestampPrecision::Milliseconds
+);
+precision_wrapper!(
+    /// Encodes a `SystemTime` or `Duration` with microseconds: `S1_700_000_000.123_456`, `1.500_000`.
+    /// Decoding accepts any precision.
+    InMicroseconds,
+    TimestampPrecision::Microseconds
+);
+precision_wrapper!(
+    /// Encodes a `SystemTime` or `Duration` with nanoseconds, the same as the unwrapped types.
+    /// Decoding accepts any precision.
+    InNanoseconds,
+    TimestampPrecision::Nanoseconds
+);
@@ -0,0 +1,46 @@
+use jtoo::{ByteBuf, Bytes, Decode, Encode, ErrorReason};
+
+#[test]
+fn bytes_encode() {
+    assert_eq!(Bytes(&[]).encode(), Ok("B".to_string()));
+    assert_eq!(Bytes(&[0x00, 0xff]).encode(), Ok("B00ff".to_string()));
+}
+
+#[test]
+fn byte_buf_encode() {
+    assert_eq!(ByteBuf::new().encode(), Ok("B".to_string()));
+    assert_eq!(
+        ByteBuf(vec![0x01, 0x23, 0xab]).encode(),
+        Ok("B0123ab".to_string())
+    );
+}
+
+#[test]
+fn byte_buf_decode() {
+    assert_eq!(ByteBuf::decode(b"B"), Ok(ByteBuf::new()));
+    assert_eq!(
+        ByteBuf::decode(b"B0123ab"),
+        Ok(ByteBuf(vec![0x01, 0x23, 0xab]))
+    );
+    assert_eq!(
+        ByteBuf::decode(b"[]").unwrap_err().reason,
+        ErrorReason::ExpectedByteString
+    );
+}
+
+#[test]
+fn array_encode() {
+    assert_eq!([0u8; 0].encode(), Ok("B".to_string()));
+    assert_eq!([0x0a_u8, 0x0b, 0x0c].encode(), Ok("B0a0b0c".to_string()));
+}
+
+#[test]
+fn array_decode() {
+    assert_eq!(<[u8; 0]>::decode(b"B"), Ok([]));
+    assert_eq!(<[u8; 3]>::decode(b"B0a0b0c"), Ok([0x0a, 0x0b, 0x0c]));
+    for bytes in [b"B".as_slice(), b"B0a0b", b"B0a0b0c0d"] {
+        let e = <[u8; 3]>::decode(bytes).unwrap_err();
+        assert_eq!(e.reason, ErrorReason::IncorrectByteStringLength);
+        assert_eq!(e.debug_bytes, bytes);
+    }
+}
@@ -1,12 +1,13 @@
-use jtoo::{Decode, DecodeError, Decoder, ErrorReason};
+use jtoo::{Decode, DecodeError, DecodeOwned, Decoder, ErrorReason};
+use std::borrow::Cow;
 
 #[test]
 fn decode() {
     // TODO: Derive Decode.
     #[derive(Debug, Eq, PartialEq)]
     struct Struct(String);
-    impl Decode for Struct {
-        fn decode_using(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+    impl<'a> Decode<'a> for Struct {
+        fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
             let value = decoder.consume_string()?;
             Ok(Self(value))
         }
@@ -14,10 +15,76 @@ fn decode() {
     assert_eq!(Struct::decode(b"\"str1\""), Ok(Struct("str1".to_string())));
     assert_eq!(
         format!("{:?}", Struct::decode(b"T").unwrap_err()),
-        "DecodeError: ExpectedString: 'T'".to_string()
+        "DecodeError: ExpectedString at line 1, column 1 (offset 0): 'T'".to_string()
     );
     assert_eq!(
         Struct::decode(b"T").unwrap_err().reason,
         ErrorReason::ExpectedString
     );
 }
+
+#[test]
+fn decode_borrowed() {
+    // TODO: Derive Decode.
+    #[derive(Debug, Eq, PartialEq)]
+    struct Struct<'a> {
+        name: &'a str,
+        note: Cow<'a, str>,
+    }
+    impl<'a> Decode<'a> for Struct<'a> {
+        fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+            decoder.consume_list_open()?;
+            let name = <&str>::decode_using(decoder)?;
+            let note = Cow::decode_using(decoder)?;
+            decoder.consume_list_close()?;
+            Ok(Self { name, note })
+        }
+    }
+    let bytes = br#"["a","b\22"]"#;
+    let value = Struct::decode(bytes).unwrap();
+    assert_eq!(
+        value,
+        Struct {
+            name: "a",
+            note: Cow::Owned("b\"".to_string())
+        }
+    );
+    assert!(bytes.as_ptr_range().contains(&value.name.as_ptr()));
+    assert_eq!(
+        Struct::decode(br#"["a\22","b"]"#).unwrap_err().reason,
+        ErrorReason::EscapedStringNotBorrowable
+    );
+}
+
+#[test]
+fn decode_owned() {
+    fn decode_owned<T: DecodeOwned>(bytes: &[u8]) -> Result<T, DecodeError> {
+        let buffer = bytes.to_vec();
+        T::decode(&buffer)
+    }
+    assert_eq!(decode_owned::<String>(b"\"a\""), Ok("a".to_string()));
+}
+
+#[test]
+fn decode_error_display() {
+    fn parse(bytes: &[u8]) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(String::decode(bytes).map_err(|e| e.with_context("Name"))?)
+    }
+    let e = parse(b"[1]").unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "expected a string at line 1, column 1 (offset 0) while decoding Name: '[1]'"
+    );
+    assert!(e.source().is_none());
+    let e = e.downcast::<DecodeError>().unwrap();
+    assert_eq!(e.reason, ErrorReason::ExpectedString);
+    assert_eq!(e.context, ["Name"]);
+    assert_eq!(
+        format!("{e:?}"),
+        "DecodeError: ExpectedString at line 1, column 1 (offset 0) while decoding Name: '[1]'"
+    );
+    assert_eq!(
+        ErrorReason::IncorrectDigitGrouping.to_string(),
+        "digits are not in groups of three"
+    );
+}
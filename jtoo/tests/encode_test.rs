@@ -24,3 +24,65 @@ fn encode_err() {
     }
     assert_eq!(Struct {}.encode(), Err(EncodeError::UnclosedString));
 }
+
+#[test]
+fn encode_error_context() {
+    struct Struct;
+    impl Encode for Struct {
+        fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+            encoder
+                .append_year(2024)
+                .and_then(|d| d.append_month(13))
+                .map(|_| ())
+                .map_err(|e| e.with_context("Struct.date"))
+        }
+    }
+    let e = Struct.encode().unwrap_err();
+    assert_eq!(e.to_string(), "invalid month while encoding Struct.date");
+    assert_eq!(e, EncodeError::InvalidMonth);
+    assert_eq!(e.root(), &EncodeError::InvalidMonth);
+    assert!(std::error::Error::source(&e).is_none());
+
+    let e = EncodeError::from(std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+        .with_context("Item.date")
+        .with_context("Order");
+    assert_eq!(
+        e.to_string(),
+        "error writing output while encoding Item.date while encoding Order"
+    );
+    let source = std::error::Error::source(&e).unwrap();
+    assert_eq!(source.to_string(), "broken pipe");
+}
+
+#[test]
+fn encode_to_writer() {
+    struct Struct;
+    impl Encode for Struct {
+        fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+            encoder.open_list()?;
+            encoder.append_integer(1)?;
+            encoder.append_bool(true)?;
+            encoder.close_list()
+        }
+    }
+    let mut bytes = Vec::new();
+    Struct.encode_to_writer(&mut bytes).unwrap();
+    assert_eq!(bytes, b"[1,T]");
+    assert_eq!("a".to_string().encode_to_writer(std::io::sink()), Ok(()));
+}
+
+#[test]
+fn encode_pretty() {
+    struct Struct;
+    impl Encode for Struct {
+        fn encode_using(&self, encoder: &mut Encoder) -> Result<(), EncodeError> {
+            encoder.open_list()?;
+            encoder.append_integer(1)?;
+            encoder.open_list()?;
+            encoder.close_list()?;
+            encoder.close_list()
+        }
+    }
+    assert_eq!(Struct.encode(), Ok("[1,[]]".to_string()));
+    assert_eq!(Struct.encode_pretty(), Ok("[\n  1,\n  []\n]".to_string()));
+}
@@ -1,4 +1,5 @@
 use jtoo::{EncodeError, Encoder};
+use std::io;
 
 #[test]
 fn empty() {
@@ -228,6 +229,68 @@ fn integer() {
     }
 }
 
+#[test]
+fn integer_in_list() {
+    let mut encoder = Encoder::new();
+    encoder.open_list().unwrap();
+    encoder.append_integer(-1).unwrap();
+    encoder.append_u64(2_000).unwrap();
+    encoder.append_i128(-3).unwrap();
+    encoder.append_u128(4).unwrap();
+    encoder.close_list().unwrap();
+    assert_eq!(encoder.into_string(), Ok("[-1,2_000,-3,4]".to_string()));
+}
+
+#[test]
+fn u64_value() {
+    for (value, expected) in [
+        (0, "0"),
+        (999, "999"),
+        (1_000, "1_000"),
+        (u64::MAX, "18_446_744_073_709_551_615"),
+    ] {
+        let mut encoder = Encoder::new();
+        encoder.append_u64(value).unwrap();
+        assert_eq!(encoder.into_string(), Ok(expected.to_string()));
+    }
+}
+
+#[test]
+fn i128_value() {
+    for (value, expected) in [
+        (0, "0"),
+        (-1_000, "-1_000"),
+        (
+            i128::MAX,
+            "170_141_183_460_469_231_731_687_303_715_884_105_727",
+        ),
+        (
+            i128::MIN,
+            "-170_141_183_460_469_231_731_687_303_715_884_105_728",
+        ),
+    ] {
+        let mut encoder = Encoder::new();
+        encoder.append_i128(value).unwrap();
+        assert_eq!(encoder.into_string(), Ok(expected.to_string()));
+    }
+}
+
+#[test]
+fn u128_value() {
+    for (value, expected) in [
+        (0, "0"),
+        (12_345, "12_345"),
+        (
+            u128::MAX,
+            "340_282_366_920_938_463_463_374_607_431_768_211_455",
+        ),
+    ] {
+        let mut encoder = Encoder::new();
+        encoder.append_u128(value).unwrap();
+        assert_eq!(encoder.into_string(), Ok(expected.to_string()));
+    }
+}
+
 #[test]
 fn list_in_string() {
     let mut encoder = Encoder::new();
@@ -388,124 +451,119 @@ fn not_in_string() {
 #[test]
 fn timestamp_seconds() {
     for (value, expected) in [
-        (0, Ok("S0")),
-        (1, Ok("S1")),
-        (10, Ok("S10")),
-        (100, Ok("S100")),
-        (1_000, Ok("S1_000")),
-        (10_000, Ok("S10_000")),
-        (100_000, Ok("S100_000")),
-        (1_000_000, Ok("S1_000_000")),
-        (1_234_567_890, Ok("S1_234_567_890")),
-        (i64::MAX as u64, Ok("S9_223_372_036_854_775_807")),
-        (i64::MAX as u64 + 1, Err(EncodeError::InvalidTimestamp)),
-        (u64::MAX, Err(EncodeError::InvalidTimestamp)),
+        (0, "S0"),
+        (1, "S1"),
+        (10, "S10"),
+        (100, "S100"),
+        (1_000, "S1_000"),
+        (10_000, "S10_000"),
+        (100_000, "S100_000"),
+        (1_000_000, "S1_000_000"),
+        (1_234_567_890, "S1_234_567_890"),
+        (i64::MAX, "S9_223_372_036_854_775_807"),
+        (-1, "S-1"),
+        (-1_234, "S-1_234"),
+        (i64::MIN, "S-9_223_372_036_854_775_808"),
     ] {
         let mut encoder = Encoder::new();
-        match expected {
-            Ok(s) => {
-                encoder.append_timestamp_seconds(value).unwrap();
-                assert_eq!(encoder.into_string(), Ok(s.to_string()));
-            }
-            Err(e) => {
-                assert_eq!(encoder.append_timestamp_seconds(value), Err(e));
-            }
-        }
+        encoder.append_timestamp_seconds(value).unwrap();
+        assert_eq!(encoder.into_string(), Ok(expected.to_string()), "{value}");
     }
 }
 
 #[test]
 fn timestamp_milliseconds() {
     for (value, expected) in [
-        (0, Ok("S0.000")),
-        (1, Ok("S0.001")),
-        (10, Ok("S0.010")),
-        (100, Ok("S0.100")),
-        (1_000, Ok("S1.000")),
-        (10_000, Ok("S10.000")),
-        (100_000, Ok("S100.000")),
-        (1_000_000, Ok("S1_000.000")),
-        (1_234_567_890, Ok("S1_234_567.890")),
-        (i64::MAX as u64, Ok("S9_223_372_036_854_775.807")),
-        (i64::MAX as u64 + 1, Err(EncodeError::InvalidTimestamp)),
-        (u64::MAX, Err(EncodeError::InvalidTimestamp)),
+        (0, "S0.000"),
+        (1, "S0.001"),
+        (10, "S0.010"),
+        (100, "S0.100"),
+        (1_000, "S1.000"),
+        (10_000, "S10.000"),
+        (100_000, "S100.000"),
+        (1_000_000, "S1_000.000"),
+        (1_234_567_890, "S1_234_567.890"),
+        (i64::MAX, "S9_223_372_036_854_775.807"),
+        (-1, "S-0.001"),
+        (-500, "S-0.500"),
+        (-1_500, "S-1.500"),
+        (i64::MIN, "S-9_223_372_036_854_775.808"),
     ] {
         let mut encoder = Encoder::new();
-        match expected {
-            Ok(s) => {
-                encoder.append_timestamp_milliseconds(value).unwrap();
-                assert_eq!(encoder.into_string(), Ok(s.to_string()));
-            }
-            Err(e) => {
-                assert_eq!(encoder.append_timestamp_milliseconds(value), Err(e));
-            }
-        }
+        encoder.append_timestamp_milliseconds(value).unwrap();
+        assert_eq!(encoder.into_string(), Ok(expected.to_string()), "{value}");
     }
 }
 
 #[test]
 fn timestamp_microseconds() {
     for (value, expected) in [
-        (0, Ok("S0.000_000")),
-        (1, Ok("S0.000_001")),
-        (10, Ok("S0.000_010")),
-        (100, Ok("S0.000_100")),
-        (1_000, Ok("S0.001_000")),
-        (10_000, Ok("S0.010_000")),
-        (100_000, Ok("S0.100_000")),
-        (1_000_000, Ok("S1.000_000")),
-        (1_234_567_890, Ok("S1_234.567_890")),
-        (i64::MAX as u64, Ok("S9_223_372_036_854.775_807")),
-        (i64::MAX as u64 + 1, Err(EncodeError::InvalidTimestamp)),
-        (u64::MAX, Err(EncodeError::InvalidTimestamp)),
+        (0, "S0.000_000"),
+        (1, "S0.000_001"),
+        (10, "S0.000_010"),
+        (100, "S0.000_100"),
+        (1_000, "S0.001_000"),
+        (10_000, "S0.010_000"),
+        (100_000, "S0.100_000"),
+        (1_000_000, "S1.000_000"),
+        (1_234_567_890, "S1_234.567_890"),
+        (i64::MAX, "S9_223_372_036_854.775_807"),
+        (-1, "S-0.000_001"),
+        (-1_500_000, "S-1.500_000"),
+        (i64::MIN, "S-9_223_372_036_854.775_808"),
     ] {
         let mut encoder = Encoder::new();
-        match expected {
-            Ok(s) => {
-                encoder.append_timestamp_microseconds(value).unwrap();
-                assert_eq!(encoder.into_string(), Ok(s.to_string()));
-            }
-            Err(e) => {
-                assert_eq!(encoder.append_timestamp_microseconds(value), Err(e));
-            }
-        }
+        encoder.append_timestamp_microseconds(value).unwrap();
+        assert_eq!(encoder.into_string(), Ok(expected.to_string()), "{value}");
     }
 }
 
 #[test]
 fn timestamp_nanoseconds() {
     for (value, expected) in [
-        (0, Ok("S0.000_000_000")),
-        (1, Ok("S0.000_000_001")),
-        (10, Ok("S0.000_000_010")),
-        (100, Ok("S0.000_000_100")),
-        (1_000, Ok("S0.000_001_000")),
-        (10_000, Ok("S0.000_010_000")),
-        (100_000, Ok("S0.000_100_000")),
-        (1_000_000, Ok("S0.001_000_000")),
-        (1_234_567_890, Ok("S1.234_567_890")),
-        (i64::MAX as u64, Ok("S9_223_372_036.854_775_807")),
-        (i64::MAX as u64 + 1, Err(EncodeError::InvalidTimestamp)),
-        (u64::MAX, Err(EncodeError::InvalidTimestamp)),
+        (0, "S0.000_000_000"),
+        (1, "S0.000_000_001"),
+        (10, "S0.000_000_010"),
+        (100, "S0.000_000_100"),
+        (1_000, "S0.000_001_000"),
+        (10_000, "S0.000_010_000"),
+        (100_000, "S0.000_100_000"),
+        (1_000_000, "S0.001_000_000"),
+        (1_234_567_890, "S1.234_567_890"),
+        (i64::MAX, "S9_223_372_036.854_775_807"),
+        (-1, "S-0.000_000_001"),
+        (-1_500_000_000, "S-1.500_000_000"),
+        (i64::MIN, "S-9_223_372_036.854_775_808"),
     ] {
         let mut encoder = Encoder::new();
-        match expected {
-            Ok(s) => {
-                encoder.append_timestamp_nanosecond(value).unwrap();
-                assert_eq!(encoder.into_string(), Ok(s.to_string()));
-            }
-            Err(e) => {
-                assert_eq!(encoder.append_timestamp_nanosecond(value), Err(e));
-            }
-        }
+        encoder.append_timestamp_nanosecond(value).unwrap();
+        assert_eq!(encoder.into_string(), Ok(expected.to_string()), "{value}");
     }
 }
 
+#[test]
+fn timestamp_in_list() {
+    let mut encoder = Encoder::new();
+    encoder.open_list().unwrap();
+    encoder.append_timestamp_seconds(1).unwrap();
+    encoder.append_timestamp_milliseconds(2).unwrap();
+    encoder.append_timestamp_microseconds(3).unwrap();
+    encoder.append_timestamp_nanosecond(4).unwrap();
+    encoder.append_timestamp_milliseconds(-5).unwrap();
+    encoder.close_list().unwrap();
+    assert_eq!(
+        encoder.into_string(),
+        Ok("[S1,S0.002,S0.000_003,S0.000_000_004,S-0.005]".to_string())
+    );
+}
+
 #[test]
 fn year() {
     for (year, expected) in [
         (0, Err(EncodeError::InvalidYear)),
-        (1, Ok("D1")),
+        (1, Ok("D0001")),
+        (12, Ok("D0012")),
+        (123, Ok("D0123")),
         (1000, Ok("D1000")),
         (2024, Ok("D2024")),
         (9999, Ok("D9999")),
@@ -529,13 +587,13 @@ fn year() {
 fn year_week() {
     for (week, expected) in [
         (0, Err(EncodeError::InvalidWeek)),
-        (1, Ok("D2021-W01")),
-        (53, Ok("D2021-W53")),
+        (1, Ok("D2020-W01")),
+        (53, Ok("D2020-W53")),
         (54, Err(EncodeError::InvalidWeek)),
         (u8::MAX, Err(EncodeError::InvalidWeek)),
     ] {
         let mut encoder = Encoder::new();
-        let appender = encoder.append_year(2021).unwrap();
+        let appender = encoder.append_year(2020).unwrap();
         match expected {
             Ok(s) => {
                 appender.append_week(week).unwrap();
@@ -548,6 +606,52 @@ fn year_week() {
     }
 }
 
+#[test]
+fn year_week_53() {
+    // 0001-01-01 is a Monday in the proleptic Gregorian calendar.
+    let mut jan_1_weekday = 1u32;
+    for year in 1..=9999u16 {
+        let is_leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let has_53_weeks = jan_1_weekday == 4 || (is_leap && jan_1_weekday == 3);
+        let mut encoder = Encoder::new();
+        let result = encoder.append_year(year).unwrap().append_week(53).err();
+        let expected = if has_53_weeks {
+            None
+        } else {
+            Some(EncodeError::InvalidWeekOfYear)
+        };
+        assert_eq!(result, expected, "year={year}");
+        let days = if is_leap { 366 } else { 365 };
+        jan_1_weekday = (jan_1_weekday + days) % 7;
+    }
+}
+
+#[test]
+fn year_month_day_leap_years() {
+    for (year, expected) in [
+        (1900, Err(EncodeError::InvalidDayOfMonth)),
+        (2000, Ok("D2000-02-29")),
+        (2023, Err(EncodeError::InvalidDayOfMonth)),
+        (2024, Ok("D2024-02-29")),
+    ] {
+        let mut encoder = Encoder::new();
+        let result = encoder
+            .append_year(year)
+            .unwrap()
+            .append_month(2)
+            .unwrap()
+            .append_day(29)
+            .map(|_| ());
+        match expected {
+            Ok(s) => {
+                result.unwrap();
+                assert_eq!(encoder.into_string(), Ok(s.to_string()), "year={year}");
+            }
+            Err(e) => assert_eq!(result, Err(e), "year={year}"),
+        }
+    }
+}
+
 #[test]
 fn year_week_weekday() {
     for (weekday, expected) in [
@@ -609,7 +713,10 @@ fn year_month_day() {
     for (month, day, expected) in [
         (1, 0, Err(EncodeError::InvalidDay)),
         (1, 1, Ok("D2024-01-01")),
-        (2, 30, Ok("D2024-02-30")),
+        (2, 29, Ok("D2024-02-29")),
+        (2, 30, Err(EncodeError::InvalidDayOfMonth)),
+        (4, 30, Ok("D2024-04-30")),
+        (4, 31, Err(EncodeError::InvalidDayOfMonth)),
         (12, 31, Ok("D2024-12-31")),
         (1, 32, Err(EncodeError::InvalidDay)),
         (1, u8::MAX, Err(EncodeError::InvalidDay)),
@@ -1031,20 +1138,21 @@ fn time_tzoffset() {
 #[test]
 fn tzoffset() {
     for (hour, minute, expected) in [
-        (-13, 0, Err(EncodeError::InvalidTimezoneOffset)),
+        (-24, 0, Err(EncodeError::InvalidTimezoneOffset)),
         (0, 60, Err(EncodeError::InvalidTimezoneOffset)),
-        (-12, 59, Ok("D2021-1259")),
-        (-12, 0, Ok("D2021-12")),
-        (-5, 30, Ok("D2021-0530")),
-        (-1, 0, Ok("D2021-01")),
-        (-1, 1, Ok("D2021-0101")),
+        (-23, 59, Ok("D2021~23:59")),
+        (-12, 0, Ok("D2021~12")),
+        (-5, 30, Ok("D2021~05:30")),
+        (-1, 0, Ok("D2021~01")),
+        (-1, 1, Ok("D2021~01:01")),
         // Cannot represent -00xx.
         (0, 0, Ok("D2021Z")),
-        (0, 1, Ok("D2021+0001")),
+        (0, 1, Ok("D2021+00:01")),
         (1, 0, Ok("D2021+01")),
-        (5, 30, Ok("D2021+0530")),
-        (12, 59, Ok("D2021+1259")),
-        (13, 0, Err(EncodeError::InvalidTimezoneOffset)),
+        (5, 30, Ok("D2021+05:30")),
+        (14, 0, Ok("D2021+14")),
+        (23, 59, Ok("D2021+23:59")),
+        (24, 0, Err(EncodeError::InvalidTimezoneOffset)),
         (0, 60, Err(EncodeError::InvalidTimezoneOffset)),
         (i8::MAX, 0, Err(EncodeError::InvalidTimezoneOffset)),
         (0, u8::MAX, Err(EncodeError::InvalidTimezoneOffset)),
@@ -1073,7 +1181,7 @@ fn tzoffset() {
             .unwrap()
             .as_str()
             .unwrap(),
-        "T04-08"
+        "T04~08"
     );
     assert_eq!(
         Encoder::new()
@@ -1087,6 +1195,197 @@ fn tzoffset() {
             .unwrap()
             .as_str()
             .unwrap(),
-        "T04:05:06.007_008_009+0530"
+        "T04:05:06.007_008_009+05:30"
+    );
+}
+
+fn encode_strings(encoder: &mut Encoder, count: usize) {
+    encoder.open_list().unwrap();
+    for n in 0..count {
+        encoder.open_string().unwrap();
+        encoder.append_string(&format!("string {n}")).unwrap();
+        encoder.close_string().unwrap();
+    }
+    encoder.close_list().unwrap();
+}
+
+/// Records the length of each write.
+#[derive(Default)]
+struct ChunkWriter {
+    bytes: Vec<u8>,
+    writes: Vec<usize>,
+    flushed: bool,
+}
+impl io::Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes.extend_from_slice(buf);
+        self.writes.push(buf.len());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flushed = true;
+        Ok(())
+    }
+}
+
+#[test]
+fn encoder_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Encoder<'static>>();
+}
+
+#[test]
+fn to_writer() {
+    let mut bytes = Vec::new();
+    let mut encoder = Encoder::to_writer(&mut bytes);
+    encoder.append_integer(1).unwrap();
+    encoder.append_record_separator().unwrap();
+    encoder.append_bool(true).unwrap();
+    encoder.finish().unwrap();
+    assert_eq!(bytes, b"1\nT");
+}
+
+#[test]
+fn to_writer_writes_chunks() {
+    let mut expected = Encoder::new();
+    encode_strings(&mut expected, 10_000);
+    let expected = expected.into_string().unwrap();
+
+    let mut writer = ChunkWriter::default();
+    let mut encoder = Encoder::to_writer(&mut writer);
+    encode_strings(&mut encoder, 10_000);
+    encoder.finish().unwrap();
+    assert!(writer.flushed);
+    assert_eq!(String::from_utf8(writer.bytes).unwrap(), expected);
+    assert!(10 < writer.writes.len(), "writes={:?}", writer.writes);
+    let (last, full) = writer.writes.split_last().unwrap();
+    assert!(full.iter().all(|len| (8 * 1024..9 * 1024).contains(len)));
+    assert!(*last < 9 * 1024, "last={last}");
+}
+
+#[test]
+fn to_writer_long_string() {
+    let s = "a".repeat(100_000);
+    let mut writer = ChunkWriter::default();
+    let mut encoder = Encoder::to_writer(&mut writer);
+    encoder.open_string().unwrap();
+    encoder.append_string(&s).unwrap();
+    encoder.close_string().unwrap();
+    encoder.finish().unwrap();
+    assert_eq!(writer.writes, [100_001, 1]);
+    assert_eq!(writer.bytes, format!("\"{s}\"").into_bytes());
+}
+
+#[test]
+fn to_writer_unclosed() {
+    let mut bytes = Vec::new();
+    let mut encoder = Encoder::to_writer(&mut bytes);
+    encoder.open_list().unwrap();
+    encoder.open_string().unwrap();
+    assert_eq!(encoder.append_bool(true), Err(EncodeError::UnclosedString));
+    encoder.close_string().unwrap();
+    assert_eq!(encoder.finish(), Err(EncodeError::UnclosedList));
+    assert_eq!(bytes, b"");
+}
+
+#[test]
+fn to_writer_error() {
+    struct FailingWriter;
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut encoder = Encoder::to_writer(FailingWriter);
+    encoder.append_integer(1).unwrap();
+    let e = encoder.finish().unwrap_err();
+    assert_eq!(
+        e,
+        EncodeError::from(io::Error::from(io::ErrorKind::BrokenPipe))
+    );
+    assert_eq!(e.to_string(), "error writing output");
+    let source = std::error::Error::source(&e).unwrap();
+    assert_eq!(source.to_string(), "broken pipe");
+
+    let mut encoder = Encoder::to_writer(FailingWriter);
+    encoder.open_list().unwrap();
+    let result = (0..10_000).try_for_each(|n| encoder.append_integer(n));
+    assert!(
+        matches!(&result, Err(EncodeError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn to_writer_into_string() {
+    let mut bytes = Vec::new();
+    let mut encoder = Encoder::to_writer(&mut bytes);
+    encoder.append_integer(1).unwrap();
+    assert_eq!(encoder.as_str(), Err(EncodeError::HasWriter));
+    assert_eq!(encoder.into_string(), Err(EncodeError::HasWriter));
+    assert_eq!(bytes, b"");
+
+    let mut s = String::new();
+    let mut encoder = Encoder::to_fmt_writer(&mut s);
+    encoder.append_integer(1).unwrap();
+    assert_eq!(encoder.as_str(), Err(EncodeError::HasWriter));
+    encoder.finish().unwrap();
+    assert_eq!(s, "1");
+}
+
+#[test]
+fn to_fmt_writer() {
+    let mut expected = Encoder::new();
+    encode_strings(&mut expected, 10_000);
+    let expected = expected.into_string().unwrap();
+
+    let mut s = String::new();
+    let mut encoder = Encoder::to_fmt_writer(&mut s);
+    encode_strings(&mut encoder, 10_000);
+    encoder.finish().unwrap();
+    assert_eq!(s, expected);
+}
+
+#[test]
+fn pretty() {
+    let mut encoder = Encoder::new().pretty();
+    encoder.append_integer(1).unwrap();
+    encoder.append_record_separator().unwrap();
+    encoder.open_list().unwrap();
+    encoder.close_list().unwrap();
+    encoder.append_record_separator().unwrap();
+    encoder.open_list().unwrap();
+    encoder.open_list().unwrap();
+    encoder.append_bool(true).unwrap();
+    encoder.open_list().unwrap();
+    encoder.close_list().unwrap();
+    encoder.close_list().unwrap();
+    encoder.append_year(2024).unwrap();
+    encoder.open_string().unwrap();
+    assert_eq!(encoder.append_bool(true), Err(EncodeError::UnclosedString));
+    encoder.append_string("a\nb").unwrap();
+    encoder.close_string().unwrap();
+    assert_eq!(encoder.as_str(), Err(EncodeError::UnclosedList));
+    encoder.close_list().unwrap();
+    assert_eq!(
+        encoder.into_string().unwrap(),
+        "1\n[]\n[\n  [\n    T,\n    []\n  ],\n  D2024,\n  \"a\\0ab\"\n]"
     );
 }
+
+#[test]
+fn pretty_to_writer() {
+    let mut bytes = Vec::new();
+    let mut encoder = Encoder::to_writer(&mut bytes).pretty();
+    encoder.open_list().unwrap();
+    encoder.append_integer(1).unwrap();
+    encoder.append_integer(2).unwrap();
+    encoder.close_list().unwrap();
+    encoder.finish().unwrap();
+    assert_eq!(bytes, b"[\n  1,\n  2\n]");
+}
@@ -0,0 +1,163 @@
+use jtoo::{Decode, Decoder, Encode, ErrorReason, PathSegment};
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::path::PathBuf;
+
+#[test]
+fn integers() {
+    assert_eq!(i8::MIN.encode(), Ok("-128".to_string()));
+    assert_eq!(i8::decode(b"-128"), Ok(i8::MIN));
+    assert_eq!(u16::MAX.encode(), Ok("65_535".to_string()));
+    assert_eq!(u16::decode(b"65_535"), Ok(u16::MAX));
+    assert_eq!(i32::decode(b"-2_147_483_648"), Ok(i32::MIN));
+    assert_eq!(
+        u64::MAX.encode(),
+        Ok("18_446_744_073_709_551_615".to_string())
+    );
+    assert_eq!(u64::decode(b"18_446_744_073_709_551_615"), Ok(u64::MAX));
+    assert_eq!(
+        i128::decode(i128::MIN.encode().unwrap().as_bytes()),
+        Ok(i128::MIN)
+    );
+    assert_eq!(
+        u128::decode(u128::MAX.encode().unwrap().as_bytes()),
+        Ok(u128::MAX)
+    );
+    assert_eq!(
+        isize::decode(isize::MIN.encode().unwrap().as_bytes()),
+        Ok(isize::MIN)
+    );
+    assert_eq!(
+        usize::decode(usize::MAX.encode().unwrap().as_bytes()),
+        Ok(usize::MAX)
+    );
+}
+
+#[test]
+fn integer_out_of_range() {
+    for (result, debug_bytes) in [
+        (i8::decode(b"128").map(|_| ()), b"128".as_slice()),
+        (i8::decode(b"-129").map(|_| ()), b"-129"),
+        (u8::decode(b"256").map(|_| ()), b"256"),
+        (
+            i64::decode(b"9_223_372_036_854_775_808").map(|_| ()),
+            b"9_223_372_036_854_775_808",
+        ),
+    ] {
+        let e = result.unwrap_err();
+        assert_eq!(e.reason, ErrorReason::IntegerTooLarge);
+        assert_eq!(e.debug_bytes, debug_bytes);
+    }
+    assert_eq!(
+        u32::decode(b"-1").unwrap_err().reason,
+        ErrorReason::NegativeNotAllowed
+    );
+}
+
+#[test]
+fn ip_addresses() {
+    let v4 = Ipv4Addr::new(192, 168, 0, 1);
+    let v6 = Ipv6Addr::LOCALHOST;
+    assert_eq!(v4.encode(), Ok(r#""192.168.0.1""#.to_string()));
+    assert_eq!(Ipv4Addr::decode(br#""192.168.0.1""#), Ok(v4));
+    assert_eq!(v6.encode(), Ok(r#""::1""#.to_string()));
+    assert_eq!(Ipv6Addr::decode(br#""::1""#), Ok(v6));
+    assert_eq!(IpAddr::decode(br#""192.168.0.1""#), Ok(IpAddr::V4(v4)));
+    assert_eq!(IpAddr::decode(br#""::1""#), Ok(IpAddr::V6(v6)));
+    let socket = SocketAddr::new(IpAddr::V6(v6), 8080);
+    assert_eq!(socket.encode(), Ok(r#""[::1]:8080""#.to_string()));
+    assert_eq!(SocketAddr::decode(br#""[::1]:8080""#), Ok(socket));
+    assert_eq!(
+        SocketAddrV4::decode(br#""192.168.0.1:80""#),
+        Ok(SocketAddrV4::new(v4, 80))
+    );
+}
+
+#[test]
+fn ip_address_malformed() {
+    for (result, reason) in [
+        (
+            IpAddr::decode(br#""192.168.0.256""#).map(|_| ()),
+            ErrorReason::MalformedIpAddress,
+        ),
+        (
+            Ipv4Addr::decode(br#""::1""#).map(|_| ()),
+            ErrorReason::MalformedIpAddress,
+        ),
+        (
+            Ipv6Addr::decode(br#""192.168.0.1""#).map(|_| ()),
+            ErrorReason::MalformedIpAddress,
+        ),
+        (
+            SocketAddr::decode(br#""::1:8080""#).map(|_| ()),
+            ErrorReason::MalformedSocketAddress,
+        ),
+        (
+            SocketAddrV6::decode(br#""192.168.0.1:80""#).map(|_| ()),
+            ErrorReason::MalformedSocketAddress,
+        ),
+        (
+            IpAddr::decode(b"1").map(|_| ()),
+            ErrorReason::ExpectedString,
+        ),
+    ] {
+        assert_eq!(result.unwrap_err().reason, reason);
+    }
+    let mut decoder = Decoder::new(br#"["::1","x"]"#);
+    decoder.consume_list_open().unwrap();
+    assert_eq!(
+        IpAddr::decode_using(&mut decoder),
+        Ok(Ipv6Addr::LOCALHOST.into())
+    );
+    let e = IpAddr::decode_using(&mut decoder).unwrap_err();
+    assert_eq!(e.reason, ErrorReason::MalformedIpAddress);
+    assert_eq!(e.debug_bytes, br#""x"]"#);
+    assert_eq!(e.path, [PathSegment::Index(1)]);
+}
+
+#[test]
+fn paths() {
+    let path = PathBuf::from("/tmp/a b.txt");
+    assert_eq!(path.encode(), Ok(r#""/tmp/a b.txt""#.to_string()));
+    assert_eq!(path.as_path().encode(), Ok(r#""/tmp/a b.txt""#.to_string()));
+    assert_eq!(PathBuf::decode(br#""/tmp/a b.txt""#), Ok(path));
+    assert_eq!(OsString::from("abc").encode(), Ok(r#""abc""#.to_string()));
+    assert_eq!(OsString::decode(br#""abc""#), Ok(OsString::from("abc")));
+    assert_eq!(
+        PathBuf::decode(b"1").unwrap_err().reason,
+        ErrorReason::ExpectedString
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn paths_not_utf8() {
+    use std::os::unix::ffi::OsStringExt;
+    let os_string = OsString::from_vec(vec![b'a', 0xff]);
+    assert_eq!(os_string.encode(), Ok("B61ff".to_string()));
+    assert_eq!(OsString::decode(b"B61ff"), Ok(os_string.clone()));
+    assert_eq!(
+        PathBuf::from(os_string.clone()).encode(),
+        Ok("B61ff".to_string())
+    );
+    assert_eq!(PathBuf::decode(b"B61ff"), Ok(PathBuf::from(os_string)));
+}
+
+#[test]
+fn strings() {
+    assert_eq!("a\"b".encode(), Ok(r#""a\22b""#.to_string()));
+    assert_eq!("ab".to_string().encode(), Ok(r#""ab""#.to_string()));
+    assert_eq!(Cow::Borrowed("ab").encode(), Ok(r#""ab""#.to_string()));
+    assert_eq!(String::decode(br#""a\22b""#), Ok("a\"b".to_string()));
+    assert_eq!(<&str>::decode(br#""ab""#), Ok("ab"));
+    assert_eq!(
+        <&str>::decode(br#""a\22b""#).unwrap_err().reason,
+        ErrorReason::EscapedStringNotBorrowable
+    );
+    assert!(matches!(Cow::decode(br#""ab""#), Ok(Cow::Borrowed("ab"))));
+    assert_eq!(
+        Cow::<str>::decode(br#""a\22b""#),
+        Ok(Cow::Owned("a\"b".to_string()))
+    );
+}
@@ -0,0 +1,113 @@
+use jtoo::{
+    escape_ascii, ByteBuf, Decoder, DecoderOptions, Encoder, ErrorReason, JtooIndex, PathSegment,
+};
+
+#[test]
+fn index_large_list() {
+    let mut encoder = Encoder::new();
+    encoder.open_list().unwrap();
+    for n in 0..50_000 {
+        encoder.open_list().unwrap();
+        encoder.append_integer(n).unwrap();
+        encoder.open_string().unwrap();
+        encoder.append_string(&format!("item {n}")).unwrap();
+        encoder.close_string().unwrap();
+        encoder.close_list().unwrap();
+    }
+    encoder.close_list().unwrap();
+    let string = encoder.into_string().unwrap();
+    let bytes = string.as_bytes();
+    let index = JtooIndex::build(bytes).unwrap();
+    assert_eq!(index.len(), 50_000);
+    assert!(!index.is_empty());
+    assert_eq!(index.offsets()[0], 1);
+    let mut decoder = index.decoder(bytes, 40_000).unwrap();
+    decoder.consume_list_open().unwrap();
+    assert_eq!(decoder.consume_integer(), Ok(40_000));
+    assert_eq!(decoder.consume_string(), Ok("item 40000".to_string()));
+    decoder.consume_list_close().unwrap();
+    assert!(index.decoder(bytes, 50_000).is_none());
+}
+
+#[test]
+fn index_decode() {
+    let bytes = b"[1,T,\"a\",[2,3],B0a]";
+    let index = JtooIndex::build(bytes).unwrap();
+    assert_eq!(index.offsets(), [1, 3, 5, 9, 15]);
+    assert_eq!(index.decode::<i64>(bytes, 0), Some(Ok(1)));
+    assert_eq!(index.decode::<&str>(bytes, 2), Some(Ok("a")));
+    assert_eq!(
+        index.decode::<ByteBuf>(bytes, 4),
+        Some(Ok(ByteBuf(vec![0x0a])))
+    );
+    let e = index.decode::<i64>(bytes, 1).unwrap().unwrap_err();
+    assert_eq!(e.reason, ErrorReason::ExpectedInteger);
+    assert_eq!(e.path, [PathSegment::Index(1)]);
+    assert_eq!(e.offset, 3);
+    assert_eq!(JtooIndex::build(b"[]").unwrap().len(), 0);
+}
+
+#[test]
+fn index_malformed() {
+    for (bytes, reason) in [
+        (b"".as_slice(), ErrorReason::ExpectedList),
+        (b"1", ErrorReason::ExpectedList),
+        (b"[1,", ErrorReason::ExpectedListEnd),
+        (b"[1,2", ErrorReason::ExpectedListEnd),
+        (b"[1,[2]", ErrorReason::ExpectedListEnd),
+        (b"[1,0x]", ErrorReason::ExpectedListSeparator),
+        (b"[1]2", ErrorReason::MalformedListEnd),
+        (b"[1,,2]", ErrorReason::ExpectedValue),
+    ] {
+        assert_eq!(
+            JtooIndex::build(bytes).unwrap_err().reason,
+            reason,
+            "{}",
+            escape_ascii(bytes)
+        );
+    }
+}
+
+#[test]
+fn index_nested_list() {
+    let bytes = b"[0,[10,11,12],2]";
+    let mut decoder = Decoder::new(bytes);
+    decoder.consume_list_open().unwrap();
+    decoder.skip_value().unwrap();
+    let index = JtooIndex::build_using(&mut decoder).unwrap();
+    assert_eq!(index.offsets(), [4, 7, 10]);
+    assert_eq!(index.decode::<i64>(bytes, 2), Some(Ok(12)));
+    assert_eq!(decoder.consume_integer(), Ok(2));
+    decoder.consume_list_close().unwrap();
+    decoder.close().unwrap();
+}
+
+#[test]
+fn index_deep_nesting() {
+    let mut bytes = b"[1,".to_vec();
+    bytes.extend(b"[".repeat(100_000));
+    bytes.extend(b"]".repeat(100_000));
+    bytes.extend(b",2]");
+    let index = JtooIndex::build(&bytes).unwrap();
+    assert_eq!(index.offsets(), [1, 3, 200_004]);
+    assert_eq!(index.decode::<i64>(&bytes, 2), Some(Ok(2)));
+}
+
+#[test]
+fn index_keeps_options() {
+    let options = DecoderOptions {
+        whitespace_and_comments: true,
+        lenient: true,
+        ..DecoderOptions::default()
+    };
+    let bytes = b"[ 1 , [ 2 , 3 ] , 1234 ]";
+    let mut decoder = Decoder::with_options(bytes, options).unwrap();
+    let index = JtooIndex::build_using(&mut decoder).unwrap();
+    decoder.close().unwrap();
+    let mut item = index.decoder(bytes, 1).unwrap();
+    item.consume_list_open().unwrap();
+    assert_eq!(item.consume_integer(), Ok(2));
+    assert_eq!(item.consume_integer(), Ok(3));
+    item.consume_list_close().unwrap();
+    assert_eq!(index.decode::<i64>(bytes, 2), Some(Ok(1_234)));
+}
@@ -0,0 +1,418 @@
+use jtoo::{
+    escape_ascii, validate_with_options, ByteBuf, Decode, DecodeError, Decoder, DecoderOptions,
+    Encoder, ErrorReason, ValueKind,
+};
+
+fn decode_all(decoder: &mut Decoder) -> Result<(), DecodeError> {
+    match decoder.peek_kind() {
+        Some(ValueKind::List) => {
+            decoder.consume_list_open()?;
+            while decoder.has_another_list_item() {
+                decode_all(decoder)?;
+            }
+            decoder.consume_list_close()
+        }
+        Some(ValueKind::ByteString) => decoder.consume_byte_string().map(|_| ()),
+        Some(ValueKind::Integer) => decoder.consume_integer().map(|_| ()),
+        _ => decoder.consume_str().map(|_| ()),
+    }
+}
+
+#[test]
+#[allow(clippy::too_many_lines)]
+fn limits() {
+    let unlimited = DecoderOptions::default();
+    for (bytes, options, expected) in [
+        (
+            b"[[]]".as_slice(),
+            DecoderOptions {
+                max_depth: 2,
+                ..unlimited
+            },
+            Ok(()),
+        ),
+        (
+            b"[[[]]]",
+            DecoderOptions {
+                max_depth: 2,
+                ..unlimited
+            },
+            Err(ErrorReason::ListTooDeep),
+        ),
+        (
+            b"[0,1]",
+            DecoderOptions {
+                max_depth: 0,
+                ..unlimited
+            },
+            Err(ErrorReason::ListTooDeep),
+        ),
+        (
+            b"\"abc\"",
+            DecoderOptions {
+                max_string_len: 3,
+                ..unlimited
+            },
+            Ok(()),
+        ),
+        (
+            b"\"abcd\"",
+            DecoderOptions {
+                max_string_len: 3,
+                ..unlimited
+            },
+            Err(ErrorReason::StringTooLong),
+        ),
+        (
+            br#""ab\22""#,
+            DecoderOptions {
+                max_string_len: 3,
+                ..unlimited
+            },
+            Ok(()),
+        ),
+        (
+            br#""ab\22\22""#,
+            DecoderOptions {
+                max_string_len: 3,
+                ..unlimited
+            },
+            Err(ErrorReason::StringTooLong),
+        ),
+        (
+            b"B0a1b",
+            DecoderOptions {
+                max_byte_string_len: 2,
+                ..unlimited
+            },
+            Ok(()),
+        ),
+        (
+            b"B0a1b2c",
+            DecoderOptions {
+                max_byte_string_len: 2,
+                ..unlimited
+            },
+            Err(ErrorReason::ByteStringTooLong),
+        ),
+        (
+            b"[1,2]",
+            DecoderOptions {
+                max_items: 3,
+                ..unlimited
+            },
+            Ok(()),
+        ),
+        (
+            b"[1,2,3]",
+            DecoderOptions {
+                max_items: 3,
+                ..unlimited
+            },
+            Err(ErrorReason::TooManyItems),
+        ),
+        (
+            b"[[],[]]",
+            DecoderOptions {
+                max_items: 2,
+                ..unlimited
+            },
+            Err(ErrorReason::TooManyItems),
+        ),
+        (
+            b"1_000",
+            DecoderOptions {
+                max_input_len: 5,
+                ..unlimited
+            },
+            Ok(()),
+        ),
+        (
+            b"10_000",
+            DecoderOptions {
+                max_input_len: 5,
+                ..unlimited
+            },
+            Err(ErrorReason::InputTooLarge),
+        ),
+    ] {
+        let msg = format!("bytes=b\"{}\" options={options:?}", escape_ascii(bytes));
+        let result = Decoder::with_options(bytes, options).and_then(|mut decoder| {
+            decode_all(&mut decoder)?;
+            decoder.close()
+        });
+        match expected {
+            Ok(()) => result.expect(&msg),
+            Err(reason) => assert_eq!(result.expect_err(&msg).reason, reason, "{msg}"),
+        }
+    }
+}
+
+#[test]
+fn limit_error_position() {
+    let options = DecoderOptions {
+        max_string_len: 1,
+        ..DecoderOptions::default()
+    };
+    let mut decoder = Decoder::with_options(br#"["a","bc"]"#, options).unwrap();
+    decoder.consume_list_open().unwrap();
+    decoder.consume_str().unwrap();
+    let e = decoder.consume_str().unwrap_err();
+    assert_eq!(e.reason, ErrorReason::StringTooLong);
+    assert_eq!(e.offset, 5);
+    assert_eq!(e.debug_bytes, br#""bc"]"#);
+}
+
+#[test]
+fn string_limit_counts_decoded_bytes() {
+    let options = DecoderOptions {
+        max_string_len: 2,
+        ..DecoderOptions::default()
+    };
+    let mut decoder = Decoder::with_options(br#"["\22\22","a\22c"]"#, options).unwrap();
+    decoder.consume_list_open().unwrap();
+    assert_eq!(decoder.consume_string(), Ok("\"\"".to_string()));
+    let e = decoder.consume_string().unwrap_err();
+    assert_eq!(e.reason, ErrorReason::StringTooLong);
+    assert_eq!(e.debug_bytes, br#""a\22c"]"#);
+}
+
+#[test]
+fn decode_with_options() {
+    let options = DecoderOptions {
+        max_byte_string_len: 1,
+        ..DecoderOptions::default()
+    };
+    assert_eq!(
+        ByteBuf::decode_with_options(b"B0a", options),
+        Ok(ByteBuf(vec![0x0a]))
+    );
+    assert_eq!(
+        ByteBuf::decode_with_options(b"B0a0b", options)
+            .unwrap_err()
+            .reason,
+        ErrorReason::ByteStringTooLong
+    );
+}
+
+#[test]
+fn lenient() {
+    let strict = DecoderOptions::default();
+    let lenient = DecoderOptions {
+        lenient: true,
+        ..strict
+    };
+    for (bytes, expected_value, expected_warnings) in [
+        (
+            b"B0A1b".as_slice(),
+            Ok(vec![0x0a, 0x1b]),
+            vec![(ErrorReason::UppercaseHexNotAllowedInByteString, 0)],
+        ),
+        (
+            b"BAB",
+            Ok(vec![0xab]),
+            vec![(ErrorReason::UppercaseHexNotAllowedInByteString, 0)],
+        ),
+        (
+            b"B0a\n",
+            Ok(vec![0x0a]),
+            vec![(ErrorReason::DataNotConsumed, 3)],
+        ),
+        (b"B0G", Err(ErrorReason::MalformedByteString), vec![]),
+        (b"B0a\n\n", Err(ErrorReason::MalformedByteString), vec![]),
+    ] {
+        let msg = format!("bytes=b\"{}\"", escape_ascii(bytes));
+        let mut decoder = Decoder::with_options(bytes, strict).unwrap();
+        assert!(decoder.consume_byte_string().is_err(), "{msg}");
+        let mut decoder = Decoder::with_options(bytes, lenient).unwrap();
+        let result = decoder.consume_byte_string();
+        match expected_value {
+            Ok(value) => assert_eq!(result, Ok(value), "{msg}"),
+            Err(reason) => assert_eq!(result.expect_err(&msg).reason, reason, "{msg}"),
+        }
+        let warnings: Vec<(ErrorReason, usize)> = decoder
+            .warnings()
+            .iter()
+            .map(|w| (w.reason, w.offset))
+            .collect();
+        assert_eq!(warnings, expected_warnings, "{msg}");
+    }
+}
+
+#[test]
+fn lenient_integer_and_tz_offset() {
+    let lenient = DecoderOptions {
+        lenient: true,
+        ..DecoderOptions::default()
+    };
+    let mut decoder =
+        Decoder::with_options(b"[1234567,-1234,1_234,T12+00,T12~00:00,T12+05]\n", lenient).unwrap();
+    decoder.consume_list_open().unwrap();
+    assert_eq!(decoder.consume_integer(), Ok(1_234_567));
+    assert_eq!(decoder.consume_integer(), Ok(-1_234));
+    assert_eq!(decoder.consume_integer(), Ok(1_234));
+    for _ in 0..3 {
+        decoder.consume_date_time_tz_offset().unwrap();
+    }
+    decoder.consume_list_close().unwrap();
+    let warnings: Vec<(ErrorReason, usize)> = decoder
+        .warnings()
+        .iter()
+        .map(|w| (w.reason, w.offset))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (ErrorReason::IncorrectDigitGrouping, 1),
+            (ErrorReason::IncorrectDigitGrouping, 9),
+            (ErrorReason::ZeroTimeZoneOffsetShouldBeZ, 21),
+            (ErrorReason::ZeroTimeZoneOffsetShouldBeZ, 28),
+            (ErrorReason::DataNotConsumed, 45),
+        ]
+    );
+    decoder.close().unwrap();
+
+    for bytes in [b"1_23".as_slice(), b"_123", b"1__000"] {
+        let mut decoder = Decoder::with_options(bytes, lenient).unwrap();
+        assert_eq!(
+            decoder.consume_integer().unwrap_err().reason,
+            ErrorReason::IncorrectDigitGrouping
+        );
+    }
+    let mut decoder = Decoder::with_options(b"T12+05:00", lenient).unwrap();
+    assert_eq!(
+        decoder.consume_date_time_tz_offset().unwrap_err().reason,
+        ErrorReason::ZeroTimeZoneMinutesShouldBeOmitted
+    );
+}
+
+#[test]
+fn lenient_warning_positions() {
+    let lenient = DecoderOptions {
+        lenient: true,
+        whitespace_and_comments: true,
+        ..DecoderOptions::default()
+    };
+    let items = vec!["1234"; 40_000].join(",");
+    let bytes = format!("[\n{items},\n  1234\n]");
+    let mut decoder = Decoder::with_options(bytes.as_bytes(), lenient).unwrap();
+    decoder.consume_list_open().unwrap();
+    while decoder.has_another_list_item() {
+        assert_eq!(decoder.consume_integer(), Ok(1_234));
+    }
+    let warnings = decoder.warnings();
+    assert_eq!(warnings.len(), 40_001);
+    let positions: Vec<(usize, usize)> = [0, 1, 39_999, 40_000]
+        .iter()
+        .map(|n| (warnings[*n].line, warnings[*n].column))
+        .collect();
+    assert_eq!(
+        positions,
+        [(2, 1), (2, 6), (2, 199_996), (3, 3)],
+        "{:?}",
+        warnings.last()
+    );
+}
+
+#[test]
+fn max_warnings() {
+    let options = DecoderOptions {
+        lenient: true,
+        max_warnings: 2,
+        ..DecoderOptions::default()
+    };
+    let mut decoder = Decoder::with_options(b"[1234,1234,1234]", options).unwrap();
+    decoder.consume_list_open().unwrap();
+    assert_eq!(decoder.consume_integer(), Ok(1_234));
+    assert_eq!(decoder.consume_integer(), Ok(1_234));
+    let e = decoder.consume_integer().unwrap_err();
+    assert_eq!(e.reason, ErrorReason::TooManyWarnings);
+    assert_eq!(e.offset, 11);
+    assert_eq!(decoder.warnings().len(), 2);
+}
+
+#[test]
+fn checkpoint_rewind_restores_warnings_and_counts() {
+    let options = DecoderOptions {
+        lenient: true,
+        max_items: 3,
+        ..DecoderOptions::default()
+    };
+    let mut decoder = Decoder::with_options(b"[1234,5]", options).unwrap();
+    decoder.consume_list_open().unwrap();
+    let checkpoint = decoder.checkpoint();
+    assert_eq!(decoder.consume_integer(), Ok(1_234));
+    assert_eq!(decoder.warnings().len(), 1);
+    decoder.rewind(&checkpoint);
+    assert!(decoder.warnings().is_empty());
+    // Items consumed before the rewind do not count toward `max_items`.
+    assert_eq!(decoder.consume_integer(), Ok(1_234));
+    assert_eq!(decoder.consume_integer(), Ok(5));
+    decoder.consume_list_close().unwrap();
+    decoder.close().unwrap();
+}
+
+#[test]
+fn whitespace_and_comments() {
+    let strict = DecoderOptions::default();
+    let relaxed = DecoderOptions {
+        whitespace_and_comments: true,
+        ..strict
+    };
+    for (bytes, expected) in [
+        (b" [ 1 , 2 ] \n".as_slice(), Ok(())),
+        (b"\t\r\n\"a # b\" # comment", Ok(())),
+        (
+            b"# config\n[\n  1, # one\n  T12:00 ,\n  D2024\t,\n  T04:05:06.007_008_009 ]\n# end",
+            Ok(()),
+        ),
+        (b"[ ]", Ok(())),
+        (b"[[ ] ,[#x\n]]", Ok(())),
+        (b"# only a comment", Err(ErrorReason::ExpectedValue)),
+        (b"[#]", Err(ErrorReason::ExpectedListEnd)),
+        (b"[1 2]", Err(ErrorReason::ExpectedListSeparator)),
+        (b"1 2", Err(ErrorReason::MalformedInteger)),
+        (b"T F", Err(ErrorReason::MalformedBool)),
+        (b"1_ 000", Err(ErrorReason::IncorrectDigitGrouping)),
+        (b"D2024 Z", Err(ErrorReason::MalformedDateTimeTzOffset)),
+        (b"[1]\n[2]", Err(ErrorReason::MalformedListEnd)),
+    ] {
+        let msg = format!("bytes=b\"{}\"", escape_ascii(bytes));
+        assert!(validate_with_options(bytes, strict).is_err(), "{msg}");
+        let result = validate_with_options(bytes, relaxed).map_err(|e| e.reason);
+        assert_eq!(result, expected, "{msg}");
+    }
+}
+
+#[test]
+fn whitespace_and_comments_reads_pretty_output() {
+    let mut encoder = Encoder::new().pretty();
+    encoder.open_list().unwrap();
+    encoder.append_integer(1).unwrap();
+    encoder.open_list().unwrap();
+    encoder.open_string().unwrap();
+    encoder.append_string("a # b").unwrap();
+    encoder.close_string().unwrap();
+    encoder.open_list().unwrap();
+    encoder.close_list().unwrap();
+    encoder.close_list().unwrap();
+    encoder.close_list().unwrap();
+    let string = encoder.into_string().unwrap();
+    assert_eq!(string, "[\n  1,\n  [\n    \"a # b\",\n    []\n  ]\n]");
+
+    let options = DecoderOptions {
+        whitespace_and_comments: true,
+        ..DecoderOptions::default()
+    };
+    let mut decoder = Decoder::with_options(string.as_bytes(), options).unwrap();
+    decoder.consume_list_open().unwrap();
+    assert_eq!(decoder.consume_integer(), Ok(1));
+    decoder.consume_list_open().unwrap();
+    assert_eq!(decoder.consume_string(), Ok("a # b".to_string()));
+    decoder.consume_list_open().unwrap();
+    assert!(!decoder.has_another_list_item());
+    decoder.consume_list_close().unwrap();
+    decoder.consume_list_close().unwrap();
+    decoder.consume_list_close().unwrap();
+    decoder.close().unwrap();
+}
@@ -0,0 +1,223 @@
+use jtoo::{
+    ByteBuf, DecodeError, DecodeOwned, DecoderOptions, ErrorReason, RecordReader, RecordValue,
+    Timestamp, TimestampPrecision,
+};
+
+fn drain<T: DecodeOwned>(reader: &mut RecordReader) -> Vec<Result<T, ErrorReason>> {
+    let mut values = Vec::new();
+    loop {
+        match reader.next_record::<T>() {
+            Ok(RecordValue::Value(value)) => values.push(Ok(value)),
+            Ok(RecordValue::NeedMoreData | RecordValue::End) => return values,
+            Err(e) => values.push(Err(e.reason)),
+        }
+    }
+}
+
+#[test]
+fn feed_one_byte_at_a_time() {
+    let bytes = b"\"a\\0ab\"\n\"[,]\"\n\"c\"";
+    let mut reader = RecordReader::new();
+    let mut values = Vec::new();
+    for b in bytes {
+        reader.feed(&[*b]);
+        values.extend(drain::<String>(&mut reader));
+    }
+    assert_eq!(values, [Ok("a\nb".to_string()), Ok("[,]".to_string())]);
+    assert_eq!(
+        reader.next_record::<String>(),
+        Ok(RecordValue::NeedMoreData)
+    );
+    reader.finish();
+    assert_eq!(
+        reader.next_record(),
+        Ok(RecordValue::Value("c".to_string()))
+    );
+    assert_eq!(reader.next_record::<String>(), Ok(RecordValue::End));
+    assert_eq!(reader.next_record::<String>(), Ok(RecordValue::End));
+}
+
+#[test]
+fn value_waits_for_newline() {
+    let mut reader = RecordReader::new();
+    reader.feed(b"\"ab\"");
+    assert_eq!(
+        reader.next_record::<String>(),
+        Ok(RecordValue::NeedMoreData)
+    );
+    reader.feed(b"\n");
+    assert_eq!(
+        reader.next_record(),
+        Ok(RecordValue::Value("ab".to_string()))
+    );
+}
+
+#[test]
+fn newline_in_string() {
+    let mut reader = RecordReader::new();
+    reader.feed(b"\"a\nb\"\n");
+    assert_eq!(
+        reader.next_record(),
+        Ok(RecordValue::Value("a\nb".to_string()))
+    );
+}
+
+#[test]
+fn trailing_newline() {
+    let mut reader = RecordReader::new();
+    reader.feed(b"B0a\n");
+    reader.finish();
+    assert_eq!(
+        reader.next_record(),
+        Ok(RecordValue::Value(ByteBuf(vec![0x0a])))
+    );
+    assert_eq!(reader.next_record::<ByteBuf>(), Ok(RecordValue::End));
+}
+
+#[test]
+fn error_skips_value() {
+    let mut reader = RecordReader::new();
+    reader.feed(b"S1\nS1x\n\nS2.000\n");
+    reader.finish();
+    assert_eq!(
+        drain::<Timestamp>(&mut reader),
+        [
+            Ok(Timestamp {
+                value: 1,
+                precision: TimestampPrecision::Seconds
+            }),
+            Err(ErrorReason::MalformedTimestamp),
+            Err(ErrorReason::ExpectedTimestamp),
+            Ok(Timestamp {
+                value: 2_000,
+                precision: TimestampPrecision::Milliseconds
+            }),
+        ]
+    );
+}
+
+#[test]
+fn error_position() {
+    let mut reader = RecordReader::new();
+    reader.feed(b"\"a\"\n\"b\"\n[\"c\",1]\n");
+    for _ in 0..2 {
+        reader.next_record::<String>().unwrap();
+    }
+    let e: DecodeError = reader.next_record::<String>().unwrap_err();
+    assert_eq!(e.reason, ErrorReason::ExpectedString);
+    assert_eq!((e.offset, e.line, e.column), (8, 3, 1));
+}
+
+#[test]
+fn input_too_large() {
+    let options = DecoderOptions {
+        max_input_len: 4,
+        ..DecoderOptions::default()
+    };
+    let mut reader = RecordReader::with_options(options);
+    reader.feed(b"1_000\n1\n");
+    assert_eq!(
+        reader.next_record::<i64>().unwrap_err().reason,
+        ErrorReason::InputTooLarge
+    );
+    assert_eq!(reader.next_record(), Ok(RecordValue::Value(1)));
+    reader.feed(b"\"abcd");
+    assert_eq!(
+        reader.next_record::<String>().unwrap_err().reason,
+        ErrorReason::InputTooLarge
+    );
+}
+
+#[test]
+fn error_line_after_newline_in_string() {
+    let mut reader = RecordReader::new();
+    reader.feed(b"\"a\nb\"\n\"c\"\nx\n");
+    for _ in 0..2 {
+        reader.next_record::<String>().unwrap();
+    }
+    let e = reader.next_record::<String>().unwrap_err();
+    assert_eq!(e.reason, ErrorReason::ExpectedString);
+    assert_eq!((e.offset, e.line, e.column), (10, 4, 1));
+}
+
+#[test]
+fn input_too_large_is_not_buffered() {
+    let options = DecoderOptions {
+        max_input_len: 10,
+        ..DecoderOptions::default()
+    };
+    let mut reader = RecordReader::with_options(options);
+    reader.feed(b"1\n\"");
+    assert_eq!(reader.next_record(), Ok(RecordValue::Value(1)));
+    for _ in 0..1000 {
+        reader.feed(b"\nabcdefghijklmnopqrst");
+    }
+    let e = reader.next_record::<String>().unwrap_err();
+    assert_eq!(e.reason, ErrorReason::InputTooLarge);
+    assert_eq!((e.offset, e.line, e.column), (2, 2, 1));
+    assert_eq!(
+        reader.next_record::<String>(),
+        Ok(RecordValue::NeedMoreData)
+    );
+    reader.feed(b"\"\n\"a\"\n");
+    assert_eq!(
+        reader.next_record(),
+        Ok(RecordValue::Value("a".to_string()))
+    );
+    assert_eq!(
+        reader.next_record::<String>(),
+        Ok(RecordValue::NeedMoreData)
+    );
+    reader.finish();
+    assert_eq!(reader.next_record::<String>(), Ok(RecordValue::End));
+}
+
+#[test]
+fn whitespace_and_comments() {
+    let options = DecoderOptions {
+        whitespace_and_comments: true,
+        ..DecoderOptions::default()
+    };
+    let mut reader = RecordReader::with_options(options);
+    reader.feed(b"1 # say \"hi\n2\n3\n");
+    assert_eq!(drain::<i64>(&mut reader), [Ok(1), Ok(2), Ok(3)]);
+
+    let mut reader = RecordReader::with_options(options);
+    for b in b"1\n\n# c\n  \n2\n# end\n" {
+        reader.feed(&[*b]);
+    }
+    assert_eq!(drain::<i64>(&mut reader), [Ok(1), Ok(2)]);
+    reader.finish();
+    assert_eq!(reader.next_record::<i64>(), Ok(RecordValue::End));
+}
+
+#[test]
+fn whitespace_and_comments_error_line() {
+    let options = DecoderOptions {
+        whitespace_and_comments: true,
+        ..DecoderOptions::default()
+    };
+    let mut reader = RecordReader::with_options(options);
+    reader.feed(b"1\n# c\n\n  x\n");
+    assert_eq!(reader.next_record(), Ok(RecordValue::Value(1)));
+    let e = reader.next_record::<i64>().unwrap_err();
+    assert_eq!(e.reason, ErrorReason::ExpectedInteger);
+    assert_eq!((e.offset, e.line, e.column), (9, 4, 3));
+}
+
+#[test]
+fn read_from() {
+    let mut input = std::io::Cursor::new(b"1\n2\n3".to_vec());
+    let mut reader = RecordReader::new();
+    let mut values = Vec::new();
+    loop {
+        match reader.next_record::<i64>().unwrap() {
+            RecordValue::Value(value) => values.push(value),
+            RecordValue::NeedMoreData => {
+                reader.read_from(&mut input).unwrap();
+            }
+            RecordValue::End => break,
+        }
+    }
+    assert_eq!(values, [1, 2, 3]);
+}
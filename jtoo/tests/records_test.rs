@@ -0,0 +1,154 @@
+use jtoo::{Decode, DecodeError, Decoder, DecoderOptions, EncodeError, Encoder, ErrorReason};
+
+#[derive(Debug, Eq, PartialEq)]
+struct Point(i64, i64);
+impl<'a> Decode<'a> for Point {
+    fn decode_using(decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+        decoder.consume_list_open()?;
+        let x = decoder.consume_integer()?;
+        let y = decoder.consume_integer()?;
+        decoder.consume_list_close()?;
+        Ok(Self(x, y))
+    }
+}
+
+#[test]
+fn records_roundtrip() {
+    let mut encoder = Encoder::new();
+    for (x, y) in [(1, 2), (3, 4), (5, 6)] {
+        encoder.open_list().unwrap();
+        encoder.append_integer(x).unwrap();
+        encoder.append_integer(y).unwrap();
+        encoder.close_list().unwrap();
+        encoder.append_record_separator().unwrap();
+    }
+    let string = encoder.into_string().unwrap();
+    assert_eq!(string, "[1,2]\n[3,4]\n[5,6]\n");
+    let mut decoder = Decoder::new(string.as_bytes());
+    let points: Result<Vec<Point>, DecodeError> = decoder.records().collect();
+    assert_eq!(points, Ok(vec![Point(1, 2), Point(3, 4), Point(5, 6)]));
+    decoder.close().unwrap();
+}
+
+#[test]
+fn records_without_trailing_newline() {
+    let mut decoder = Decoder::new(b"\"a\nb\"\n\"c\"");
+    let strings: Result<Vec<String>, DecodeError> = decoder.records().collect();
+    assert_eq!(strings, Ok(vec!["a\nb".to_string(), "c".to_string()]));
+    assert_eq!(Decoder::new(b"").records::<String>().count(), 0);
+}
+
+#[test]
+fn records_skip_errors() {
+    let bytes = b"[1,2]\n[3]\n\n[4,5]x\n[\"\\0g\n\",1]\n[6,7]";
+    let mut decoder = Decoder::new(bytes);
+    let results: Vec<Result<Point, (ErrorReason, usize)>> = decoder
+        .records()
+        .map(|result| result.map_err(|e| (e.reason, e.offset)))
+        .collect();
+    assert_eq!(
+        results,
+        [
+            Ok(Point(1, 2)),
+            Err((ErrorReason::ExpectedInteger, 8)),
+            Err((ErrorReason::ExpectedList, 10)),
+            Err((ErrorReason::MalformedListEnd, 15)),
+            Err((ErrorReason::ExpectedInteger, 19)),
+            Ok(Point(6, 7)),
+        ]
+    );
+    decoder.close().unwrap();
+}
+
+#[test]
+fn records_skip_many_errors() {
+    let bytes = format!("{}[1,2]\n  x", "[1]\n".repeat(40_000));
+    let mut decoder = Decoder::new(bytes.as_bytes());
+    let results: Vec<Result<Point, (ErrorReason, usize, usize)>> = decoder
+        .records()
+        .map(|result| result.map_err(|e| (e.reason, e.line, e.column)))
+        .collect();
+    assert_eq!(results.len(), 40_002);
+    assert_eq!(results[0], Err((ErrorReason::ExpectedInteger, 1, 3)));
+    assert_eq!(
+        results[39_999],
+        Err((ErrorReason::ExpectedInteger, 40_000, 3))
+    );
+    assert_eq!(results[40_000], Ok(Point(1, 2)));
+    assert_eq!(results[40_001], Err((ErrorReason::ExpectedList, 40_002, 1)));
+}
+
+#[test]
+fn records_must_consume_value() {
+    struct Nothing;
+    impl<'a> Decode<'a> for Nothing {
+        fn decode_using(_decoder: &mut Decoder<'a>) -> Result<Self, DecodeError> {
+            Ok(Self)
+        }
+    }
+    let mut decoder = Decoder::new(b"1\n2");
+    let reasons: Vec<ErrorReason> = decoder
+        .records::<Nothing>()
+        .map(|result| result.err().unwrap().reason)
+        .collect();
+    assert_eq!(
+        reasons,
+        [ErrorReason::DataNotConsumed, ErrorReason::DataNotConsumed]
+    );
+}
+
+#[test]
+fn record_separator_outside_value() {
+    let mut encoder = Encoder::new();
+    encoder.open_list().unwrap();
+    assert_eq!(
+        encoder.append_record_separator(),
+        Err(EncodeError::UnclosedList)
+    );
+    encoder.open_string().unwrap();
+    assert_eq!(
+        encoder.append_record_separator(),
+        Err(EncodeError::UnclosedString)
+    );
+}
+
+#[test]
+fn records_with_whitespace_and_comments() {
+    let options = DecoderOptions {
+        whitespace_and_comments: true,
+        ..DecoderOptions::default()
+    };
+    let bytes = b"# points\n[1, 2] # first\n\n  # blank line\n[3 ,4]\n[5,\n6]\n[7,8]  \n";
+    let mut decoder = Decoder::with_options(bytes, options).unwrap();
+    let results: Vec<Result<Point, ErrorReason>> = decoder
+        .records()
+        .map(|result| result.map_err(|e| e.reason))
+        .collect();
+    assert_eq!(
+        results,
+        [
+            Ok(Point(1, 2)),
+            Ok(Point(3, 4)),
+            Err(ErrorReason::ExpectedInteger),
+            Err(ErrorReason::ExpectedList),
+            Ok(Point(7, 8)),
+        ]
+    );
+    decoder.close().unwrap();
+}
+
+#[test]
+fn records_skip_to_next_line_after_comment() {
+    let options = DecoderOptions {
+        whitespace_and_comments: true,
+        ..DecoderOptions::default()
+    };
+    let bytes = b"x # say \"hi\n2\n\n# c\n3\n";
+    let mut decoder = Decoder::with_options(bytes, options).unwrap();
+    let results: Vec<Result<i64, ErrorReason>> = decoder
+        .records()
+        .map(|result| result.map_err(|e| e.reason))
+        .collect();
+    assert_eq!(results, [Err(ErrorReason::ExpectedInteger), Ok(2), Ok(3)]);
+    decoder.close().unwrap();
+}
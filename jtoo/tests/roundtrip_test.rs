@@ -0,0 +1,385 @@
+use core::fmt::Debug;
+use jtoo::{
+    ByteBuf, Date, DateTimeTzOffset, Decode, DecodeOwned, Decoder, Encode, EncodeError, Encoder,
+    ErrorReason, InNanoseconds, Time, Timestamp, TimestampPrecision, TzOffset,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A deterministic xorshift generator, so failures are reproducible.
+struct Rng(u64);
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+fn roundtrip<T: Encode + DecodeOwned + Debug + PartialEq>(value: &T) {
+    let encoded = value
+        .encode()
+        .unwrap_or_else(|e| panic!("value={value:?} encode error: {e}"));
+    let decoded = T::decode(encoded.as_bytes())
+        .unwrap_or_else(|e| panic!("value={value:?} encoded={encoded:?} decode error: {e}"));
+    assert_eq!(&decoded, value, "encoded={encoded:?}");
+}
+
+#[test]
+fn integers() {
+    for n in i16::MIN..=i16::MAX {
+        roundtrip(&n);
+    }
+    for n in u16::MIN..=u16::MAX {
+        roundtrip(&n);
+    }
+    let mut rng = Rng(1);
+    for _ in 0..10_000 {
+        let bits = rng.next();
+        let shift = rng.below(64);
+        roundtrip(&(bits.cast_signed() >> shift));
+        roundtrip(&(bits >> shift));
+        let wide = u128::from(bits) << 64 | u128::from(rng.next());
+        roundtrip(&(wide.cast_signed() >> shift));
+        roundtrip(&(wide >> shift));
+    }
+    for n in [i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX] {
+        roundtrip(&n);
+    }
+    for n in [i128::MIN, i128::MIN + 1, -1, 0, 1, i128::MAX] {
+        roundtrip(&n);
+    }
+    roundtrip(&u64::MAX);
+    roundtrip(&u128::MAX);
+}
+
+#[test]
+fn bools() {
+    for b in [false, true] {
+        let mut encoder = Encoder::new();
+        encoder.append_bool(b).unwrap();
+        let string = encoder.into_string().unwrap();
+        let mut decoder = Decoder::new(string.as_bytes());
+        assert_eq!(decoder.consume_bool(), Ok(b));
+        decoder.close().unwrap();
+    }
+}
+
+#[test]
+fn decimals() {
+    let mut rng = Rng(2);
+    for base10_exponent in -18..=0i8 {
+        for _ in 0..1_000 {
+            let value = (rng.next().cast_signed()) >> rng.below(64);
+            let mut encoder = Encoder::new();
+            encoder.append_decimal(value, base10_exponent).unwrap();
+            let string = encoder.into_string().unwrap();
+            let mut decoder = Decoder::new(string.as_bytes());
+            let (mantissa, exponent) = decoder.consume_decimal().unwrap();
+            // `1.0` is `(1, 0)` and also `(10, -1)`, so this compares numeric values.
+            let scale = |mantissa: i64, exponent: i8| {
+                i128::from(mantissa) * 10i128.pow(u32::from(exponent.abs_diff(-18)))
+            };
+            assert_eq!(
+                scale(mantissa, exponent),
+                scale(value, base10_exponent),
+                "string={string:?}"
+            );
+            decoder.close().unwrap();
+        }
+    }
+}
+
+#[test]
+fn strings() {
+    for c in (0..=0x7f_u8)
+        .map(char::from)
+        .chain(['é', '€', '𝄞', '\u{10FFFF}'])
+    {
+        roundtrip(&c.to_string());
+        roundtrip(&format!("a{c}b"));
+    }
+    let alphabet: Vec<char> = (0..=0x7f_u8).map(char::from).chain(['é', '€']).collect();
+    let mut rng = Rng(3);
+    for _ in 0..1_000 {
+        let len = rng.below(40);
+        let s: String = (0..len)
+            .map(|_| alphabet[usize::try_from(rng.below(alphabet.len() as u64)).unwrap()])
+            .collect();
+        roundtrip(&s);
+    }
+}
+
+#[test]
+fn byte_strings() {
+    roundtrip(&ByteBuf(vec![]));
+    roundtrip(&ByteBuf((0..=u8::MAX).collect()));
+    let mut rng = Rng(4);
+    for _ in 0..1_000 {
+        let len = rng.below(40);
+        let bytes: Vec<u8> = (0..len).map(|_| rng.next().to_le_bytes()[0]).collect();
+        roundtrip(&ByteBuf(bytes));
+    }
+}
+
+#[test]
+fn timestamps() {
+    let mut rng = Rng(5);
+    for precision in [
+        TimestampPrecision::Seconds,
+        TimestampPrecision::Milliseconds,
+        TimestampPrecision::Microseconds,
+        TimestampPrecision::Nanoseconds,
+    ] {
+        let values = [i64::MIN, i64::MIN + 1, -1_001, -1, 0, 1, 999, i64::MAX];
+        let random = (0..1_000).map(|_| rng.next().cast_signed() >> rng.below(64));
+        for value in values.into_iter().chain(random) {
+            roundtrip(&Timestamp { value, precision });
+        }
+    }
+}
+
+#[test]
+fn system_times_and_durations() {
+    let mut rng = Rng(6);
+    for _ in 0..1_000 {
+        let nanos = rng.below(1 << 62);
+        let duration = Duration::from_nanos(nanos);
+        roundtrip(&duration);
+        roundtrip(&InNanoseconds(duration));
+        roundtrip(&(UNIX_EPOCH + duration));
+        roundtrip(&(UNIX_EPOCH - duration));
+    }
+    roundtrip(&UNIX_EPOCH);
+    roundtrip(
+        &SystemTime::UNIX_EPOCH
+            .checked_add(Duration::new(1, 1))
+            .unwrap(),
+    );
+}
+
+fn date(value: Date) -> DateTimeTzOffset {
+    DateTimeTzOffset::Date(value)
+}
+
+#[test]
+fn years_and_months() {
+    for y in 1..=9999 {
+        roundtrip(&date(Date::Year { y }));
+        for mo in 1..=12 {
+            roundtrip(&date(Date::YearMonth { y, mo }));
+        }
+    }
+}
+
+#[test]
+fn year_month_days() {
+    // Every day of every year would take too long, so this covers every day in
+    // years with each kind of leap-year rule, and the edges of February for every year.
+    for y in [1, 4, 1900, 2000, 2023, 2024, 9999] {
+        for mo in 1..=12 {
+            for d in 1..=31 {
+                let value = date(Date::YearMonthDay { y, mo, d });
+                if value.encode() != Err(EncodeError::InvalidDayOfMonth) {
+                    roundtrip(&value);
+                }
+            }
+        }
+    }
+    for y in 1..=9999 {
+        for d in [1, 28, 29] {
+            let value = date(Date::YearMonthDay { y, mo: 2, d });
+            if value.encode() != Err(EncodeError::InvalidDayOfMonth) {
+                roundtrip(&value);
+            }
+        }
+    }
+}
+
+#[test]
+fn year_weeks() {
+    for y in 1..=9999 {
+        for w in 1..=53 {
+            let value = date(Date::YearWeek { y, w });
+            if value.encode() != Err(EncodeError::InvalidWeekOfYear) {
+                roundtrip(&value);
+            }
+        }
+    }
+    for y in [1, 2020, 2021, 9999] {
+        for w in [1, 52] {
+            for d in 1..=7 {
+                roundtrip(&date(Date::YearWeekDay { y, w, d }));
+            }
+        }
+    }
+}
+
+#[test]
+fn times() {
+    for h in 0..=23 {
+        roundtrip(&DateTimeTzOffset::Time(Time::Hour { h }));
+        for m in 0..=59 {
+            roundtrip(&DateTimeTzOffset::Time(Time::HourMinute { h, m }));
+            for s in 0..=60 {
+                roundtrip(&DateTimeTzOffset::Time(Time::HourMinuteSecond { h, m, s }));
+            }
+        }
+    }
+    let mut rng = Rng(7);
+    for _ in 0..10_000 {
+        let h = u8::try_from(rng.below(24)).unwrap();
+        let m = u8::try_from(rng.below(60)).unwrap();
+        let ms = u16::try_from(rng.below(61_000)).unwrap();
+        let us = u32::try_from(rng.below(61_000_000)).unwrap();
+        let ns = rng.below(61_000_000_000);
+        roundtrip(&DateTimeTzOffset::Time(Time::HourMinuteMillisecond {
+            h,
+            m,
+            ms,
+        }));
+        roundtrip(&DateTimeTzOffset::Time(Time::HourMinuteMicrosecond {
+            h,
+            m,
+            us,
+        }));
+        roundtrip(&DateTimeTzOffset::Time(Time::HourMinuteNanosecond {
+            h,
+            m,
+            ns,
+        }));
+    }
+}
+
+#[test]
+fn tz_offsets() {
+    let time = Time::HourMinute { h: 12, m: 34 };
+    let day = Date::YearMonthDay {
+        y: 2024,
+        mo: 2,
+        d: 29,
+    };
+    for h in -23..=23 {
+        for m in 0..=59 {
+            let tz = TzOffset { h, m };
+            roundtrip(&DateTimeTzOffset::TimeTz(time, tz));
+            roundtrip(&DateTimeTzOffset::DateTz(Date::Year { y: 2024 }, tz));
+            roundtrip(&DateTimeTzOffset::DateTimeTz(day, time, tz));
+        }
+    }
+}
+
+#[test]
+fn dates_times_and_tz_offsets() {
+    let dates = [
+        Date::Year { y: 1 },
+        Date::YearMonth { y: 2024, mo: 12 },
+        Date::YearWeek { y: 2020, w: 53 },
+        Date::YearMonthDay {
+            y: 9999,
+            mo: 12,
+            d: 31,
+        },
+        Date::YearWeekDay {
+            y: 2021,
+            w: 1,
+            d: 7,
+        },
+    ];
+    let times = [
+        Time::Hour { h: 0 },
+        Time::HourMinute { h: 23, m: 59 },
+        Time::HourMinuteSecond { h: 1, m: 2, s: 60 },
+        Time::HourMinuteMillisecond { h: 1, m: 2, ms: 3 },
+        Time::HourMinuteMicrosecond {
+            h: 1,
+            m: 2,
+            us: 60_999_999,
+        },
+        Time::HourMinuteNanosecond { h: 1, m: 2, ns: 0 },
+    ];
+    let tzs = [
+        TzOffset { h: 0, m: 0 },
+        TzOffset { h: -23, m: 59 },
+        TzOffset { h: 5, m: 30 },
+    ];
+    for date in dates {
+        roundtrip(&DateTimeTzOffset::Date(date));
+        for tz in tzs {
+            roundtrip(&DateTimeTzOffset::DateTz(date, tz));
+        }
+        for time in times {
+            let value = DateTimeTzOffset::DateTime(date, time);
+            if date.day().is_none() {
+                assert_eq!(value.encode(), Err(EncodeError::InvalidDateTime));
+                continue;
+            }
+            roundtrip(&value);
+            for tz in tzs {
+                roundtrip(&DateTimeTzOffset::DateTimeTz(date, time, tz));
+            }
+        }
+    }
+    for time in times {
+        roundtrip(&DateTimeTzOffset::Time(time));
+        for tz in tzs {
+            roundtrip(&DateTimeTzOffset::TimeTz(time, tz));
+        }
+    }
+}
+
+/// The encoder accepts exactly the dates that the decoder accepts.
+#[test]
+fn encoder_and_decoder_agree_on_dates() {
+    for y in [1, 1900, 2000, 2020, 2021, 2024, 9999] {
+        for mo in 0..=13 {
+            for d in 0..=32 {
+                let mut encoder = Encoder::new();
+                let appended = encoder
+                    .append_year(y)
+                    .and_then(|year| year.append_month(mo))
+                    .and_then(|month| month.append_day(d));
+                let bytes = format!("D{y:04}-{mo:02}-{d:02}");
+                let decoded = DateTimeTzOffset::decode(bytes.as_bytes());
+                assert_eq!(appended.is_ok(), decoded.is_ok(), "{bytes}");
+            }
+        }
+        for w in 0..=54 {
+            let mut encoder = Encoder::new();
+            let appended = encoder.append_year(y).and_then(|year| year.append_week(w));
+            let bytes = format!("D{y:04}-W{w:02}");
+            let decoded = DateTimeTzOffset::decode(bytes.as_bytes());
+            assert_eq!(appended.is_ok(), decoded.is_ok(), "{bytes}");
+        }
+    }
+}
+
+/// The encoder accepts exactly the time zone offsets that the decoder accepts.
+#[test]
+fn encoder_and_decoder_agree_on_tz_offsets() {
+    for h in -30..=30_i8 {
+        for m in 0..=60 {
+            let mut encoder = Encoder::new();
+            let appended = encoder
+                .append_hour(12)
+                .and_then(|hour| hour.append_tzoffset(h, m))
+                .map(|_| ());
+            let sign = if h < 0 { '~' } else { '+' };
+            let bytes = if m == 0 {
+                format!("T12{sign}{:02}", h.unsigned_abs())
+            } else {
+                format!("T12{sign}{:02}:{m:02}", h.unsigned_abs())
+            };
+            let decoded = DateTimeTzOffset::decode(bytes.as_bytes()).map_err(|e| e.reason);
+            match decoded {
+                // The encoder writes `Z` instead.
+                Err(ErrorReason::ZeroTimeZoneOffsetShouldBeZ) => assert_eq!(appended, Ok(())),
+                _ => assert_eq!(appended.is_ok(), decoded.is_ok(), "{bytes}"),
+            }
+        }
+    }
+}
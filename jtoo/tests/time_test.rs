@@ -0,0 +1,144 @@
+use jtoo::{
+    Decode, Encode, ErrorReason, InMicroseconds, InMilliseconds, InNanoseconds, InSeconds,
+    Timestamp, TimestampPrecision,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[test]
+fn system_time() {
+    let time = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+    assert_eq!(time.encode(), Ok("S1_700_000_000.123_456_789".to_string()));
+    assert_eq!(SystemTime::decode(b"S1_700_000_000.123_456_789"), Ok(time));
+    assert_eq!(
+        SystemTime::decode(b"S1_700_000_000"),
+        Ok(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    );
+    assert_eq!(
+        SystemTime::decode(b"T").unwrap_err().reason,
+        ErrorReason::ExpectedTimestamp
+    );
+}
+
+#[test]
+fn system_time_before_epoch() {
+    let time = UNIX_EPOCH - Duration::from_millis(1_500);
+    assert_eq!(time.encode(), Ok("S-1.500_000_000".to_string()));
+    assert_eq!(SystemTime::decode(b"S-1.500"), Ok(time));
+    assert_eq!(InSeconds(time).encode(), Ok("S-2".to_string()));
+}
+
+#[test]
+fn system_time_precision() {
+    let time = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+    assert_eq!(InSeconds(time).encode(), Ok("S1_700_000_000".to_string()));
+    assert_eq!(
+        InMilliseconds(time).encode(),
+        Ok("S1_700_000_000.123".to_string())
+    );
+    assert_eq!(
+        InMicroseconds(time).encode(),
+        Ok("S1_700_000_000.123_456".to_string())
+    );
+    assert_eq!(
+        InNanoseconds(time).encode(),
+        Ok("S1_700_000_000.123_456_789".to_string())
+    );
+    assert_eq!(
+        InMilliseconds::<SystemTime>::decode(b"S1_700_000_000.123"),
+        Ok(InMilliseconds(
+            UNIX_EPOCH + Duration::new(1_700_000_000, 123_000_000)
+        ))
+    );
+    assert_eq!(
+        InSeconds::<SystemTime>::decode(b"S1_700_000_000.123_456_789"),
+        Ok(InSeconds(time))
+    );
+}
+
+#[test]
+fn timestamp_system_time() {
+    for (time, precision, value) in [
+        (UNIX_EPOCH, TimestampPrecision::Seconds, 0),
+        (
+            UNIX_EPOCH + Duration::from_nanos(1_999_999_999),
+            TimestampPrecision::Seconds,
+            1,
+        ),
+        (
+            UNIX_EPOCH - Duration::from_nanos(1),
+            TimestampPrecision::Seconds,
+            -1,
+        ),
+        (
+            UNIX_EPOCH - Duration::from_nanos(1_000_001),
+            TimestampPrecision::Milliseconds,
+            -2,
+        ),
+        (
+            UNIX_EPOCH - Duration::from_millis(1),
+            TimestampPrecision::Milliseconds,
+            -1,
+        ),
+    ] {
+        let timestamp = Timestamp::from_system_time(time, precision).unwrap();
+        assert_eq!(timestamp, Timestamp { value, precision });
+    }
+    let timestamp = Timestamp {
+        value: -1_500,
+        precision: TimestampPrecision::Milliseconds,
+    };
+    assert_eq!(
+        timestamp.to_system_time(),
+        Some(UNIX_EPOCH - Duration::from_millis(1_500))
+    );
+}
+
+#[test]
+fn duration() {
+    assert_eq!(Duration::ZERO.encode(), Ok("0.000_000_000".to_string()));
+    assert_eq!(
+        Duration::from_millis(1_500).encode(),
+        Ok("1.500_000_000".to_string())
+    );
+    assert_eq!(
+        InSeconds(Duration::from_millis(90_999)).encode(),
+        Ok("90.0".to_string())
+    );
+    assert_eq!(
+        InMilliseconds(Duration::from_micros(1_500_999)).encode(),
+        Ok("1.500".to_string())
+    );
+    assert_eq!(
+        InMicroseconds(Duration::from_nanos(1_500_999)).encode(),
+        Ok("0.001_500".to_string())
+    );
+    assert_eq!(
+        Duration::MAX.encode().unwrap_err(),
+        jtoo::EncodeError::InvalidDuration
+    );
+    for (bytes, expected) in [
+        (b"0.0".as_slice(), Ok(Duration::ZERO)),
+        (b"90.0", Ok(Duration::from_secs(90))),
+        (b"1.5", Ok(Duration::from_millis(1_500))),
+        (b"1.500", Ok(Duration::from_millis(1_500))),
+        (b"0.000_000_001", Ok(Duration::from_nanos(1))),
+        (b"0.000_000_001_000", Ok(Duration::from_nanos(1))),
+        (b"0.000_000_000_1", Err(ErrorReason::DurationTooPrecise)),
+        (b"-1.0", Err(ErrorReason::NegativeNotAllowed)),
+        (b"1", Err(ErrorReason::ExpectedDecimal)),
+    ] {
+        let result = Duration::decode(bytes);
+        match expected {
+            Ok(value) => assert_eq!(result, Ok(value)),
+            Err(reason) => {
+                let e = result.unwrap_err();
+                assert_eq!(e.reason, reason);
+                assert_eq!(e.debug_bytes, bytes);
+            }
+        }
+    }
+    assert_eq!(
+        InSeconds::<Duration>::decode(b"1.500"),
+        Ok(InSeconds(Duration::from_millis(1_500)))
+    );
+}
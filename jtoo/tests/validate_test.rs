@@ -0,0 +1,121 @@
+use jtoo::{escape_ascii, validate, validate_with_options, DecoderOptions, ErrorReason};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn validate_values() {
+    for (bytes, expected) in [
+        (b"1".as_slice(), Ok(())),
+        (b"-1_234.5", Ok(())),
+        (b"\"abc\"", Ok(())),
+        (b"\"a\\22\\5cb\"", Ok(())),
+        (b"B00ff", Ok(())),
+        (b"D2024-01-02T12:00Z", Ok(())),
+        (b"S1_700_000_000.123", Ok(())),
+        (b"[]", Ok(())),
+        (b"[1,\"a\",[B00,[]],T01]", Ok(())),
+        (b"", Err(ErrorReason::ExpectedValue)),
+        (b"1234", Err(ErrorReason::IncorrectDigitGrouping)),
+        (b"\"a\\41\"", Err(ErrorReason::InvalidEscapeSequence)),
+        (b"\"a", Err(ErrorReason::UnclosedString)),
+        (b"B0", Err(ErrorReason::MalformedByteString)),
+        (b"D1", Err(ErrorReason::MalformedDate)),
+        (b"[1,[2]", Err(ErrorReason::ExpectedListEnd)),
+        (b"[1,]", Err(ErrorReason::ExpectedValue)),
+        (b"[1,1234]", Err(ErrorReason::IncorrectDigitGrouping)),
+        (b"1,2", Err(ErrorReason::MalformedInteger)),
+    ] {
+        let msg = format!("bytes=b\"{}\"", escape_ascii(bytes));
+        assert_eq!(validate(bytes).map_err(|e| e.reason), expected, "{msg}");
+    }
+}
+
+#[test]
+fn validate_with_limits() {
+    let options = DecoderOptions {
+        max_string_len: 2,
+        max_byte_string_len: 2,
+        max_depth: 1,
+        ..DecoderOptions::default()
+    };
+    for (bytes, expected) in [
+        (b"\"\\22\\22\"".as_slice(), Ok(())),
+        (b"\"abc\"", Err(ErrorReason::StringTooLong)),
+        (b"B0102", Ok(())),
+        (b"B010203", Err(ErrorReason::ByteStringTooLong)),
+        (b"[1]", Ok(())),
+        (b"[[]]", Err(ErrorReason::ListTooDeep)),
+    ] {
+        let msg = format!("bytes=b\"{}\"", escape_ascii(bytes));
+        assert_eq!(
+            validate_with_options(bytes, options).map_err(|e| e.reason),
+            expected,
+            "{msg}"
+        );
+    }
+}
+
+#[test]
+fn validate_deep_nesting() {
+    let mut bytes = b"[".repeat(100_000);
+    bytes.extend(b"]".repeat(100_000));
+    assert_eq!(validate(&bytes), Ok(()));
+    bytes.pop();
+    assert_eq!(
+        validate(&bytes).unwrap_err().reason,
+        ErrorReason::ExpectedListEnd
+    );
+}
+
+#[test]
+fn validate_does_not_allocate() {
+    for bytes in [
+        b"1_234".as_slice(),
+        b"\"abc\"",
+        b"\"a\\22\\0ab\"",
+        b"B0001020304050607",
+        b"D2024-01-02T12:00Z",
+    ] {
+        let msg = format!("bytes=b\"{}\"", escape_ascii(bytes));
+        assert_eq!(count_allocations(|| validate(bytes).unwrap()), 0, "{msg}");
+    }
+}
+
+#[test]
+fn validate_allocates_only_list_stack() {
+    // The stack of open lists is allocated once, with room for a few levels.
+    let bytes = b"[\"a\\22\",B0102,[\"b\",[3]],4]";
+    let allocations = count_allocations(|| validate(bytes).unwrap());
+    assert_eq!(
+        allocations,
+        1,
+        "bytes=b\"{}\" allocations={allocations}",
+        escape_ascii(bytes)
+    );
+}
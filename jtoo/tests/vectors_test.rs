@@ -0,0 +1,293 @@
+use jtoo::{
+    Date, DateTimeTzOffset, DecodeError, Decoder, Encode, EncodeError, Encoder, ErrorReason, Time,
+    Timestamp, TimestampPrecision, TzOffset, ValueKind,
+};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Test vectors live in `tests/vectors/*.txt`.  See `tests/vectors/README.md` for the format
+/// and where they come from.
+const VECTORS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors");
+
+#[derive(Debug)]
+enum Value {
+    Bool(bool),
+    Int(i128),
+    UInt(u128),
+    Decimal(i64, i8),
+    String(String),
+    Bytes(Vec<u8>),
+    Timestamp(Timestamp),
+    DateTime(DateTimeTzOffset),
+    List(Vec<Value>),
+}
+
+fn decode_value(decoder: &mut Decoder) -> Result<Value, DecodeError> {
+    match decoder.peek_kind() {
+        Some(ValueKind::Bool) => decoder.consume_bool().map(Value::Bool),
+        Some(ValueKind::Integer) => {
+            let checkpoint = decoder.checkpoint();
+            match decoder.consume_i128() {
+                Err(e) if e.reason == ErrorReason::IntegerTooLarge => {
+                    decoder.rewind(&checkpoint);
+                    decoder.consume_u128().map(Value::UInt).map_err(|_| e)
+                }
+                result => result.map(Value::Int),
+            }
+        }
+        Some(ValueKind::Decimal) => decoder
+            .consume_decimal()
+            .map(|(value, exponent)| Value::Decimal(value, exponent)),
+        Some(ValueKind::String) => decoder.consume_string().map(Value::String),
+        Some(ValueKind::ByteString) => decoder.consume_byte_string().map(Value::Bytes),
+        Some(ValueKind::DateTime) => decoder.consume_date_time_tz_offset().map(Value::DateTime),
+        Some(ValueKind::Timestamp) => decoder.consume_timestamp().map(Value::Timestamp),
+        Some(ValueKind::List) => {
+            decoder.consume_list_open()?;
+            let mut items = Vec::new();
+            while decoder.has_another_list_item() {
+                items.push(decode_value(decoder)?);
+            }
+            decoder.consume_list_close()?;
+            Ok(Value::List(items))
+        }
+        Some(ValueKind::ListEnd | ValueKind::End) | None => decoder.skip_value().map(|()| {
+            unreachable!();
+        }),
+    }
+}
+
+fn decode_document(bytes: &[u8]) -> Result<Value, DecodeError> {
+    let mut decoder = Decoder::new(bytes);
+    let value = decode_value(&mut decoder)?;
+    decoder.close()?;
+    Ok(value)
+}
+
+fn encode_value(encoder: &mut Encoder, value: &Value) -> Result<(), EncodeError> {
+    match value {
+        Value::Bool(b) => encoder.append_bool(*b),
+        Value::Int(n) => encoder.append_i128(*n),
+        Value::UInt(n) => encoder.append_u128(*n),
+        Value::Decimal(value, exponent) => encoder.append_decimal(*value, *exponent),
+        Value::String(s) => {
+            encoder.open_string()?;
+            encoder.append_string(s)?;
+            encoder.close_string()
+        }
+        Value::Bytes(bytes) => {
+            encoder.open_byte_string()?;
+            encoder.append_byte_string(bytes)?;
+            encoder.close_byte_string()
+        }
+        Value::Timestamp(timestamp) => timestamp.encode_using(encoder),
+        Value::DateTime(date_time) => date_time.encode_using(encoder),
+        Value::List(items) => {
+            encoder.open_list()?;
+            for item in items {
+                encode_value(encoder, item)?;
+            }
+            encoder.close_list()
+        }
+    }
+}
+
+fn percent_encode(out: &mut String, bytes: &[u8]) {
+    for &b in bytes {
+        if b.is_ascii_graphic() && b != b'%' && b != b'"' || b == b' ' {
+            out.push(char::from(b));
+        } else {
+            write!(out, "%{b:02x}").unwrap();
+        }
+    }
+}
+
+fn percent_decode(s: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%' {
+            let hex = tail
+                .get(..2)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| format!("invalid percent escape in {s:?}"))?;
+            bytes.push(hex);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+    Ok(bytes)
+}
+
+fn render_date(out: &mut String, date: Date) {
+    match date {
+        Date::Year { y } => write!(out, "{y:04}"),
+        Date::YearMonth { y, mo } => write!(out, "{y:04}-{mo:02}"),
+        Date::YearMonthDay { y, mo, d } => write!(out, "{y:04}-{mo:02}-{d:02}"),
+        Date::YearWeek { y, w } => write!(out, "{y:04}-W{w:02}"),
+        Date::YearWeekDay { y, w, d } => write!(out, "{y:04}-W{w:02}-{d}"),
+    }
+    .unwrap();
+}
+
+fn render_time(out: &mut String, time: Time) {
+    match time {
+        Time::Hour { h } => write!(out, "{h:02}"),
+        Time::HourMinute { h, m } => write!(out, "{h:02}:{m:02}"),
+        Time::HourMinuteSecond { h, m, s } => write!(out, "{h:02}:{m:02}:{s:02}"),
+        Time::HourMinuteMillisecond { h, m, ms } => {
+            write!(out, "{h:02}:{m:02}:{:02}.{:03}", ms / 1_000, ms % 1_000)
+        }
+        Time::HourMinuteMicrosecond { h, m, us } => {
+            write!(
+                out,
+                "{h:02}:{m:02}:{:02}.{:06}",
+                us / 1_000_000,
+                us % 1_000_000
+            )
+        }
+        Time::HourMinuteNanosecond { h, m, ns } => write!(
+            out,
+            "{h:02}:{m:02}:{:02}.{:09}",
+            ns / 1_000_000_000,
+            ns % 1_000_000_000
+        ),
+    }
+    .unwrap();
+}
+
+fn render_date_time(out: &mut String, date_time: &DateTimeTzOffset) {
+    let mut fields = Vec::new();
+    if let Some(date) = date_time.date() {
+        let mut field = String::from("date=");
+        render_date(&mut field, *date);
+        fields.push(field);
+    }
+    if let Some(time) = date_time.time() {
+        let mut field = String::from("time=");
+        render_time(&mut field, *time);
+        fields.push(field);
+    }
+    if let Some(TzOffset { h, m }) = date_time.tz_offset() {
+        fields.push(format!("tz={h:+03}:{m:02}"));
+    }
+    write!(out, "dt:{}", fields.join(",")).unwrap();
+}
+
+fn render(out: &mut String, value: &Value) {
+    match value {
+        Value::Bool(b) => write!(out, "{b}").unwrap(),
+        Value::Int(n) => write!(out, "int:{n}").unwrap(),
+        Value::UInt(n) => write!(out, "int:{n}").unwrap(),
+        Value::Decimal(value, exponent) => write!(out, "dec:{value}e{exponent}").unwrap(),
+        Value::String(s) => {
+            out.push_str("str:\"");
+            percent_encode(out, s.as_bytes());
+            out.push('"');
+        }
+        Value::Bytes(bytes) => {
+            out.push_str("bytes:");
+            for b in bytes {
+                write!(out, "{b:02x}").unwrap();
+            }
+        }
+        Value::Timestamp(Timestamp { value, precision }) => {
+            let exponent = match precision {
+                TimestampPrecision::Seconds => 0,
+                TimestampPrecision::Milliseconds => -3,
+                TimestampPrecision::Microseconds => -6,
+                TimestampPrecision::Nanoseconds => -9,
+            };
+            write!(out, "ts:{value}e{exponent}").unwrap();
+        }
+        Value::DateTime(date_time) => render_date_time(out, date_time),
+        Value::List(items) => {
+            out.push('[');
+            for (n, item) in items.iter().enumerate() {
+                if n != 0 {
+                    out.push(',');
+                }
+                render(out, item);
+            }
+            out.push(']');
+        }
+    }
+}
+
+/// Checks one vector line, returning a description of the mismatch.
+fn check_line(line: &str) -> Result<(), String> {
+    let mut fields = line.split('\t');
+    let (Some(outcome), Some(document), Some(expected), None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err("expected three tab-separated fields".to_string());
+    };
+    let bytes = percent_decode(document)?;
+    let result = decode_document(&bytes);
+    match (outcome, result) {
+        ("ok", Ok(value)) => {
+            let mut rendered = String::new();
+            render(&mut rendered, &value);
+            if rendered != expected {
+                return Err(format!("decoded {rendered}, expected {expected}"));
+            }
+            let mut encoder = Encoder::new();
+            let output = encode_value(&mut encoder, &value)
+                .and_then(|()| encoder.into_string())
+                .map_err(|e| format!("encoding {value:?} failed: {e}"))?;
+            if output.as_bytes() != bytes {
+                let mut rendered = String::new();
+                percent_encode(&mut rendered, output.as_bytes());
+                return Err(format!("re-encoded as {rendered}"));
+            }
+            Ok(())
+        }
+        ("ok", Err(e)) => Err(format!("decoding failed: {e}")),
+        ("err", Ok(value)) => Err(format!("decoded {value:?}, expected {expected}")),
+        ("err", Err(e)) => {
+            let reason = format!("{:?}", e.reason);
+            if reason == expected {
+                Ok(())
+            } else {
+                Err(format!("failed with {reason}, expected {expected}"))
+            }
+        }
+        (other, _) => Err(format!("unknown outcome {other:?}")),
+    }
+}
+
+fn check_file(path: &Path, failures: &mut Vec<String>) -> usize {
+    let contents = std::fs::read_to_string(path).unwrap();
+    let name = path.file_name().unwrap().to_string_lossy();
+    let mut count = 0;
+    for (n, line) in contents.lines().enumerate() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        count += 1;
+        if let Err(e) = check_line(line) {
+            failures.push(format!("{name}:{}: {e}", n + 1));
+        }
+    }
+    count
+}
+
+#[test]
+fn vectors() {
+    let mut paths: Vec<_> = std::fs::read_dir(VECTORS_DIR)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no vectors in {VECTORS_DIR}");
+    let mut failures = Vec::new();
+    for path in &paths {
+        let count = check_file(path, &mut failures);
+        assert_ne!(count, 0, "{} has no vectors", path.display());
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}